@@ -3,7 +3,6 @@ use std::sync::Arc;
 
 use anyhow::Result;
 use bytes::Bytes;
-use tokio::net::TcpStream;
 use tokio::time::{sleep, Duration};
 use tracing::Level;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
@@ -56,9 +55,7 @@ async fn main() -> Result<()> {
         get_version(Protocol::IPad),
         DefaultHandler,
     ));
-    let stream = TcpStream::connect(client.get_address())
-        .await
-        .expect("failed to connect");
+    let stream = client.connect().await.expect("failed to connect");
     let c = client.clone();
     let handle = tokio::spawn(async move { c.start(stream).await });
     tokio::task::yield_now().await; // 等一下，确保连上了
@@ -158,12 +155,12 @@ async fn main() -> Result<()> {
             .reload_friends()
             .await
             .expect("failed to reload friend list");
-        tracing::info!("{:?}", client.friends.read().await);
+        tracing::info!("{:?}", client.cached_friends().await);
         client
             .reload_groups()
             .await
             .expect("failed to reload group list");
-        tracing::info!("{:?}", client.groups.read().await);
+        tracing::info!("{:?}", client.cached_groups().await);
     }
 
     handle.await.unwrap();
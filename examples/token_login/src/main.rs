@@ -2,7 +2,6 @@ use std::path::Path;
 use std::sync::Arc;
 
 use anyhow::Result;
-use tokio::net::TcpStream;
 use tracing::Level;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -59,9 +58,7 @@ async fn main() -> Result<()> {
         get_version(Protocol::IPad),
         DefaultHandler,
     ));
-    let stream = TcpStream::connect(client.get_address())
-        .await
-        .expect("failed to connect");
+    let stream = client.connect().await.expect("failed to connect");
     let c = client.clone();
     let handle = tokio::spawn(async move { c.start(stream).await });
     tokio::task::yield_now().await; // 等一下，确保连上了
@@ -77,12 +74,12 @@ async fn main() -> Result<()> {
             .reload_friends()
             .await
             .expect("failed to reload friend list");
-        tracing::info!("{:?}", client.friends.read().await);
+        tracing::info!("{:?}", client.cached_friends().await);
         client
             .reload_groups()
             .await
             .expect("failed to reload group list");
-        let group_list = client.groups.read().await;
+        let group_list = client.cached_groups().await;
         tracing::info!("{:?}", group_list);
     }
     let d = client.get_allowed_clients().await;
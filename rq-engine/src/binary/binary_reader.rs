@@ -3,9 +3,12 @@ use std::collections::HashMap;
 use bytes::{Buf, Bytes};
 
 pub trait BinaryReader {
+    fn bytes_left(&self) -> usize;
+    fn is_exhausted(&self) -> bool;
     fn read_string(&mut self) -> String;
     fn read_string_short(&mut self) -> String;
     fn read_bytes_short(&mut self) -> Bytes;
+    fn read_tlv(&mut self) -> (u16, Bytes);
     fn read_tlv_map(&mut self, tag_size: usize) -> HashMap<u16, Bytes>;
     fn read_string_limit(&mut self, limit: usize) -> String;
 }
@@ -14,6 +17,15 @@ impl<B> BinaryReader for B
 where
     B: Buf,
 {
+    // 剩余可读字节数，和 Buf::remaining 相同，避免同名方法在调用处产生歧义
+    fn bytes_left(&self) -> usize {
+        self.remaining()
+    }
+
+    fn is_exhausted(&self) -> bool {
+        !self.has_remaining()
+    }
+
     fn read_string(&mut self) -> String {
         let len = self.get_i32() as usize - 4;
         String::from_utf8_lossy(&self.copy_to_bytes(len)).to_string()
@@ -29,6 +41,12 @@ where
         self.copy_to_bytes(len)
     }
 
+    // 读取 write_tlv 写入的 tag(u16) + len(u16) + body
+    fn read_tlv(&mut self) -> (u16, Bytes) {
+        let tag = self.get_u16();
+        (tag, self.read_bytes_short())
+    }
+
     fn read_tlv_map(&mut self, tag_size: usize) -> HashMap<u16, Bytes> {
         let mut m = HashMap::new();
         loop {
@@ -61,3 +79,24 @@ where
         String::from_utf8_lossy(&self.copy_to_bytes(limit)).to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+
+    use super::*;
+    use crate::binary::BinaryWriter;
+
+    #[test]
+    fn tlv_round_trip() {
+        let mut w = BytesMut::new();
+        w.write_tlv(0x1, b"abc").write_tlv(0x106, &[1, 2]);
+        let mut r = w.clone().freeze();
+        assert_eq!(r.read_tlv(), (0x1, Bytes::from_static(b"abc")));
+        assert_eq!(r.read_tlv(), (0x106, Bytes::from_static(&[1, 2])));
+        assert!(!r.has_remaining());
+
+        let m = w.freeze().read_tlv_map(2);
+        assert_eq!(m.get(&0x106), Some(&Bytes::from_static(&[1, 2])));
+    }
+}
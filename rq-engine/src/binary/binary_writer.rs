@@ -3,49 +3,55 @@ use crate::hex::decode_hex;
 use bytes::{Buf, BufMut, BytesMut};
 
 pub trait BinaryWriter {
-    fn write_bytes_short(&mut self, data: &[u8]);
-    fn encrypt_and_write(&mut self, key: &[u8], data: &[u8]);
-    fn write_hex(&mut self, h: &str);
-    fn write_int_lv_packet(&mut self, offset: usize, data: &[u8]);
-    fn write_string(&mut self, v: &str);
+    fn write_bytes_short(&mut self, data: &[u8]) -> &mut Self;
+    fn encrypt_and_write(&mut self, key: &[u8], data: &[u8]) -> &mut Self;
+    fn write_hex(&mut self, h: &str) -> &mut Self;
+    fn write_int_lv_packet(&mut self, offset: usize, data: &[u8]) -> &mut Self;
+    fn write_string(&mut self, v: &str) -> &mut Self;
     fn write_uni_packet(
         &mut self,
         command_name: &str,
         session_id: &[u8],
         extra_data: &[u8],
         body: &[u8],
-    );
-    fn write_tlv_limited_size(&mut self, data: &[u8], limit: isize);
+    ) -> &mut Self;
+    fn write_tlv_limited_size(&mut self, data: &[u8], limit: isize) -> &mut Self;
+    fn write_tlv(&mut self, tag: u16, body: &[u8]) -> &mut Self;
 }
 
 impl<B> BinaryWriter for B
 where
     B: BufMut,
 {
-    fn write_bytes_short(&mut self, data: &[u8]) {
+    fn write_bytes_short(&mut self, data: &[u8]) -> &mut Self {
         self.put_u16(data.len() as u16);
-        self.put_slice(data.chunk())
+        self.put_slice(data.chunk());
+        self
     }
 
-    fn encrypt_and_write(&mut self, key: &[u8], data: &[u8]) {
+    fn encrypt_and_write(&mut self, key: &[u8], data: &[u8]) -> &mut Self {
         let ed = qqtea_encrypt(data, key);
-        self.put_slice(&ed)
+        self.put_slice(&ed);
+        self
     }
 
-    fn write_hex(&mut self, h: &str) {
+    fn write_hex(&mut self, h: &str) -> &mut Self {
         let b = decode_hex(h).expect("write_hex failed");
         self.put_slice(&b);
+        self
     }
 
-    fn write_int_lv_packet(&mut self, offset: usize, data: &[u8]) {
+    fn write_int_lv_packet(&mut self, offset: usize, data: &[u8]) -> &mut Self {
         self.put_u32((data.len() + offset) as u32);
         self.put_slice(data);
+        self
     }
 
-    fn write_string(&mut self, v: &str) {
+    fn write_string(&mut self, v: &str) -> &mut Self {
         let payload = v.as_bytes();
         self.put_u32((payload.len() + 4) as u32);
-        self.put_slice(payload)
+        self.put_slice(payload);
+        self
     }
 
     fn write_uni_packet(
@@ -54,7 +60,7 @@ where
         session_id: &[u8],
         extra_data: &[u8],
         body: &[u8],
-    ) {
+    ) -> &mut Self {
         let mut w1 = BytesMut::new();
         {
             w1.write_string(command_name);
@@ -72,13 +78,19 @@ where
         self.put_slice(&w1);
         self.put_u32((body.len() + 4) as u32);
         self.put_slice(body);
+        self
     }
 
-    fn write_tlv_limited_size(&mut self, data: &[u8], limit: isize) {
+    fn write_tlv_limited_size(&mut self, data: &[u8], limit: isize) -> &mut Self {
         if data.len() <= limit as usize {
-            self.write_bytes_short(data);
-            return;
+            return self.write_bytes_short(data);
         }
         self.write_bytes_short(&data[..(limit as usize)])
     }
+
+    // tag(u16) + len(u16) + body
+    fn write_tlv(&mut self, tag: u16, body: &[u8]) -> &mut Self {
+        self.put_u16(tag);
+        self.write_bytes_short(body)
+    }
 }
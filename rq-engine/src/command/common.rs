@@ -32,6 +32,73 @@ impl Engine {
         let seq = self.next_seq();
         self.uni_packet_with_seq(seq as i32, command, body)
     }
+
+    /// 一次构建多个互不依赖的数据包，只需要获取一次 engine 的锁
+    pub fn batch_build_packets(&self, requests: Vec<PacketRequest>) -> Vec<Packet> {
+        requests
+            .into_iter()
+            .map(|req| match req {
+                PacketRequest::ClientRegister => self.build_client_register_packet(),
+                PacketRequest::DeviceList => self.build_device_list_request_packet(),
+                PacketRequest::FriendGroupList {
+                    friend_start_index,
+                    friend_list_count,
+                    group_start_index,
+                    group_list_count,
+                } => self.build_friend_group_list_request_packet(
+                    friend_start_index,
+                    friend_list_count,
+                    group_start_index,
+                    group_list_count,
+                ),
+                PacketRequest::GroupList { vec_cookie } => {
+                    self.build_group_list_request_packet(&vec_cookie)
+                }
+                PacketRequest::GroupInfo { group_codes } => {
+                    self.build_group_info_request_packet(group_codes)
+                }
+                PacketRequest::GroupMemberList {
+                    group_code,
+                    next_uin,
+                } => self.build_group_member_list_request_packet(group_code, next_uin),
+                PacketRequest::GetOfflineMsg { last_message_time } => {
+                    self.build_get_offline_msg_request_packet(last_message_time)
+                }
+                PacketRequest::SyncMsg { last_message_time } => {
+                    self.build_sync_msg_request_packet(last_message_time)
+                }
+            })
+            .collect()
+    }
+}
+
+/// batch_build_packets 支持的数据包
+#[derive(Debug, Clone)]
+pub enum PacketRequest {
+    ClientRegister,
+    DeviceList,
+    FriendGroupList {
+        friend_start_index: i16,
+        friend_list_count: i16,
+        group_start_index: i16,
+        group_list_count: i16,
+    },
+    GroupList {
+        vec_cookie: Vec<u8>,
+    },
+    GroupInfo {
+        group_codes: Vec<i64>,
+    },
+    GroupMemberList {
+        group_code: i64,
+        next_uin: i64,
+    },
+    GetOfflineMsg {
+        last_message_time: i64,
+    },
+    SyncMsg {
+        last_message_time: i64,
+    },
 }
 
 pub fn pack_uni_request_data(data: &[u8]) -> Bytes {
@@ -4,11 +4,13 @@ use bytes::Bytes;
 use jcers::JcePut;
 
 use crate::command::common::pack_uni_request_data;
+use crate::commands;
 use crate::jce;
 use crate::protocol::packet::Packet;
 
 impl super::super::super::Engine {
     // ConfigPushSvc.PushResp
+    #[must_use]
     pub fn build_conf_push_resp_packet(&self, t: i32, pkt_seq: i64, jce_buf: Bytes) -> Packet {
         let mut req = jcers::JceMut::new();
         req.put_i32(t, 1);
@@ -27,6 +29,6 @@ impl super::super::super::Engine {
             status: Default::default(),
             ..Default::default()
         };
-        self.uni_packet("ConfigPushSvc.PushResp", pkt.freeze())
+        self.uni_packet(commands::CONFIG_PUSH_SVC_PUSH_RESP, pkt.freeze())
     }
 }
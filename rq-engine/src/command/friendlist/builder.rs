@@ -4,12 +4,14 @@ use bytes::{BufMut, Bytes, BytesMut};
 use jcers::JcePut;
 
 use crate::command::common::pack_uni_request_data;
+use crate::commands;
 use crate::common::group_code2uin;
 use crate::protocol::packet::*;
-use crate::{jce, pb};
+use crate::{jce, pb, RQError, RQResult};
 
 impl super::super::super::Engine {
     // friendlist.getFriendGroupList
+    #[must_use]
     pub fn build_friend_group_list_request_packet(
         &self,
         friend_start_index: i16,
@@ -66,10 +68,11 @@ impl super::super::super::Engine {
             status: Default::default(),
             ..Default::default()
         };
-        self.uni_packet("friendlist.getFriendGroupList", pkt.freeze())
+        self.uni_packet(commands::FRIENDLIST_GET_FRIEND_GROUP_LIST, pkt.freeze())
     }
 
     // friendlist.GetTroopListReqV2
+    #[must_use]
     pub fn build_group_list_request_packet(&self, vec_cookie: &[u8]) -> Packet {
         let req = jce::TroopListRequest {
             uin: self.uin(),
@@ -100,10 +103,11 @@ impl super::super::super::Engine {
             status: Default::default(),
             ..Default::default()
         };
-        self.uni_packet("friendlist.GetTroopListReqV2", pkt.freeze())
+        self.uni_packet(commands::FRIENDLIST_GET_TROOP_LIST_REQ_V2, pkt.freeze())
     }
 
     // friendlist.GetTroopMemberListReq
+    #[must_use]
     pub fn build_group_member_list_request_packet(&self, group_code: i64, next_uin: i64) -> Packet {
         let payload = jce::TroopMemberListRequest {
             uin: self.uin(),
@@ -128,10 +132,11 @@ impl super::super::super::Engine {
             s_buffer: buf.freeze(),
             ..Default::default()
         };
-        self.uni_packet("friendlist.GetTroopMemberListReq", pkt.freeze())
+        self.uni_packet(commands::FRIENDLIST_GET_TROOP_MEMBER_LIST_REQ, pkt.freeze())
     }
 
     // friendlist.ModifyGroupCardReq
+    #[must_use]
     pub fn build_edit_group_tag_packet(
         &self,
         group_code: i64,
@@ -162,10 +167,11 @@ impl super::super::super::Engine {
             s_buffer: buf.freeze(),
             ..Default::default()
         };
-        self.uni_packet("friendlist.ModifyGroupCardReq", pkt.freeze())
+        self.uni_packet(commands::FRIENDLIST_MODIFY_GROUP_CARD_REQ, pkt.freeze())
     }
 
     // friendlist.DelFriend
+    #[must_use]
     pub fn build_delete_friend_packet(&self, del_uin: i64) -> Packet {
         let payload = jce::DelFriendReq {
             uin: self.uin(),
@@ -187,6 +193,74 @@ impl super::super::super::Engine {
             ..Default::default()
         };
 
-        self.uni_packet("friendlist.delFriend", pkt.freeze())
+        self.uni_packet(commands::FRIENDLIST_DEL_FRIEND, pkt.freeze())
+    }
+
+    // friendlist.SetGroupReq
+    // 分组名长度只占 1 字节，超过 255 字节返回 InvalidArgument
+    pub fn build_rename_friend_group_packet(
+        &self,
+        group_id: u8,
+        new_name: &str,
+    ) -> RQResult<Packet> {
+        let name_len = u8::try_from(new_name.len()).map_err(|_| {
+            RQError::InvalidArgument(format!(
+                "friend group name is too long ({} bytes, max 255)",
+                new_name.len()
+            ))
+        })?;
+        let mut body = BytesMut::new();
+        body.put_u8(group_id);
+        body.put_u8(name_len);
+        body.put_slice(new_name.as_bytes());
+        let payload = jce::SetGroupReq {
+            reqtype: 2,
+            uin: self.uin(),
+            vec_body: body.freeze(),
+        };
+        let buf = jce::RequestDataVersion3 {
+            map: HashMap::from([(
+                "SetGroupReq".to_string(),
+                pack_uni_request_data(&payload.freeze()),
+            )]),
+        };
+        let pkt = jce::RequestPacket {
+            i_version: 3,
+            i_request_id: self.next_packet_seq(),
+            s_servant_name: "mqq.IMService.FriendListServiceServantObj".to_string(),
+            s_func_name: "SetGroupReq".to_string(),
+            s_buffer: buf.freeze(),
+            ..Default::default()
+        };
+        Ok(self.uni_packet(commands::FRIENDLIST_SET_GROUP_REQ, pkt.freeze()))
+    }
+
+    // friendlist.MovGroupMemReq
+    #[must_use]
+    pub fn build_move_friend_to_group_packet(&self, uin: i64, group_id: u8) -> Packet {
+        let mut body = BytesMut::new();
+        body.put_u16(1);
+        body.put_u64(uin as u64);
+        body.put_u8(group_id);
+        let payload = jce::MovGroupMemReq {
+            uin: self.uin(),
+            reqtype: 0,
+            vec_body: body.freeze(),
+        };
+        let buf = jce::RequestDataVersion3 {
+            map: HashMap::from([(
+                "MovGroupMemReq".to_string(),
+                pack_uni_request_data(&payload.freeze()),
+            )]),
+        };
+        let pkt = jce::RequestPacket {
+            i_version: 3,
+            i_request_id: self.next_packet_seq(),
+            s_servant_name: "mqq.IMService.FriendListServiceServantObj".to_string(),
+            s_func_name: "MovGroupMemReq".to_string(),
+            s_buffer: buf.freeze(),
+            ..Default::default()
+        };
+        self.uni_packet(commands::FRIENDLIST_MOV_GROUP_MEM_REQ, pkt.freeze())
     }
 }
@@ -2,7 +2,7 @@ use bytes::{Buf, Bytes};
 use jcers::Jce;
 
 use crate::command::friendlist::*;
-use crate::structs::{FriendInfo, GroupInfo, GroupMemberInfo, GroupMemberPermission};
+use crate::structs::{FriendGroup, FriendInfo, GroupInfo, GroupMemberInfo, GroupMemberPermission};
 use crate::{jce, RQError, RQResult};
 
 impl super::super::super::Engine {
@@ -22,6 +22,7 @@ impl super::super::super::Engine {
         let mut r = Jce::new(&mut fl_resp);
         let total_friend_count: i16 = r.get_by_tag(5).map_err(RQError::from)?;
         let friends: Vec<jce::FriendInfo> = r.get_by_tag(7).map_err(RQError::from)?; // FIXME jce bug
+        let groups: Vec<jce::FriendGroupInfo> = r.get_by_tag(14).unwrap_or_default();
         Ok(FriendListResponse {
             total_count: total_friend_count,
             list: friends
@@ -31,6 +32,15 @@ impl super::super::super::Engine {
                     nick: f.nick,
                     remark: f.remark,
                     face_id: f.face_id,
+                    group_id: f.group_id,
+                })
+                .collect(),
+            groups: groups
+                .into_iter()
+                .map(|g| FriendGroup {
+                    id: g.group_id,
+                    name: g.group_name,
+                    member_count: g.friend_count,
                 })
                 .collect(),
         })
@@ -126,4 +136,34 @@ impl super::super::super::Engine {
             .ok_or_else(|| RQError::Decode("decode_remove_friend `DFRESP` not found".into()))?;
         jcers::from_buf(&mut r).map_err(RQError::Jce)
     }
+
+    // friendlist.SetGroupReq
+    pub fn decode_rename_friend_group_response(&self, mut payload: Bytes) -> RQResult<()> {
+        let mut req: jce::RequestPacket = jcers::from_buf(&mut payload)?;
+        let mut data: jce::RequestDataVersion3 = jcers::from_buf(&mut req.s_buffer)?;
+        let mut r = data.map.remove("SetGroupResp").ok_or_else(|| {
+            RQError::Decode("decode_rename_friend_group_response `SetGroupResp` not found".into())
+        })?;
+        let resp: jce::SetGroupResp = jcers::from_buf(&mut r)?;
+        if resp.result != 0 {
+            return Err(RQError::UnsuccessfulRetCode(resp.result as i32));
+        }
+        Ok(())
+    }
+
+    // friendlist.MovGroupMemReq
+    pub fn decode_move_friend_to_group_response(&self, mut payload: Bytes) -> RQResult<()> {
+        let mut req: jce::RequestPacket = jcers::from_buf(&mut payload)?;
+        let mut data: jce::RequestDataVersion3 = jcers::from_buf(&mut req.s_buffer)?;
+        let mut r = data.map.remove("MovGroupMemResp").ok_or_else(|| {
+            RQError::Decode(
+                "decode_move_friend_to_group_response `MovGroupMemResp` not found".into(),
+            )
+        })?;
+        let resp: jce::MovGroupMemResp = jcers::from_buf(&mut r)?;
+        if resp.result != 0 {
+            return Err(RQError::UnsuccessfulRetCode(resp.result as i32));
+        }
+        Ok(())
+    }
 }
@@ -9,6 +9,8 @@ pub mod decoder;
 pub struct FriendListResponse {
     pub list: Vec<FriendInfo>,
     pub total_count: i16,
+    // 只有 group_list_count > 0 时才有
+    pub groups: Vec<FriendGroup>,
 }
 
 #[derive(Debug)]
@@ -1,9 +1,11 @@
 use crate::command::common::PbToBytes;
+use crate::commands;
 use crate::pb;
 use crate::protocol::packet::Packet;
 
 impl super::super::super::Engine {
     // group_anonymous_generate_nick.group
+    #[must_use]
     pub fn build_get_anony_info_request(&self, group_code: i64) -> Packet {
         let req = pb::cmd0x3bb::AnonyMsg {
             cmd: Some(1),
@@ -13,6 +15,9 @@ impl super::super::super::Engine {
             }),
             ..Default::default()
         };
-        self.uni_packet("group_anonymous_generate_nick.group", req.to_bytes())
+        self.uni_packet(
+            commands::GROUP_ANONYMOUS_GENERATE_NICK_GROUP,
+            req.to_bytes(),
+        )
     }
 }
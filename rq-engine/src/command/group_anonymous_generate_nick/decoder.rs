@@ -7,8 +7,7 @@ use crate::{pb, RQError, RQResult};
 impl super::super::super::Engine {
     // group_member_card.get_group_member_card_info
     pub fn decode_get_anony_info_response(&self, payload: Bytes) -> RQResult<Option<Anonymous>> {
-        let resp = pb::cmd0x3bb::AnonyMsg::from_bytes(&payload)
-            .map_err(|_| RQError::Decode("AnonyMsg".to_string()))?;
+        let resp = pb::cmd0x3bb::AnonyMsg::from_bytes(&payload)?;
         let rsp = resp
             .anony_rsp
             .ok_or_else(|| RQError::Other("missing anony_rsp".into()))?;
@@ -1,9 +1,11 @@
 use crate::command::common::PbToBytes;
+use crate::commands;
 use crate::pb;
 use crate::protocol::packet::*;
 
 impl super::super::super::Engine {
     // group_member_card.get_group_member_card_info
+    #[must_use]
     pub fn build_group_member_info_request_packet(&self, group_code: i64, uin: i64) -> Packet {
         let payload = pb::GroupMemberReqBody {
             group_code,
@@ -13,7 +15,7 @@ impl super::super::super::Engine {
             rich_card_name_ver: 1,
         };
         self.uni_packet(
-            "group_member_card.get_group_member_card_info",
+            commands::GROUP_MEMBER_CARD_GET_GROUP_MEMBER_CARD_INFO,
             payload.to_bytes(),
         )
     }
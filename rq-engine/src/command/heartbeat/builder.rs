@@ -1,14 +1,16 @@
+use crate::commands;
 use crate::protocol::packet::*;
 
 impl super::super::super::Engine {
     // Heartbeat.Alive
+    #[must_use]
     pub fn build_heartbeat_packet(&self) -> Packet {
         let seq = self.next_seq();
         Packet {
             packet_type: PacketType::Login,
             encrypt_type: EncryptType::NoEncrypt,
             seq_id: seq as i32,
-            command_name: "Heartbeat.Alive".into(),
+            command_name: commands::HEARTBEAT_ALIVE.into(),
             uin: self.uin(),
             ..Default::default()
         }
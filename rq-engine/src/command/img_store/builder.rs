@@ -1,8 +1,10 @@
 use crate::command::common::PbToBytes;
+use crate::commands;
 use crate::pb;
 use crate::protocol::packet::Packet;
 
 impl super::super::super::Engine {
+    #[must_use]
     pub fn build_group_image_store_packet(
         &self,
         group_code: i64,
@@ -36,6 +38,6 @@ impl super::super::super::Engine {
             extension: Some(vec![]),
             ..Default::default()
         };
-        self.uni_packet("ImgStore.GroupPicUp", req.to_bytes())
+        self.uni_packet(commands::IMG_STORE_GROUP_PIC_UP, req.to_bytes())
     }
 }
@@ -1,8 +1,10 @@
 use crate::command::common::PbToBytes;
+use crate::commands;
 use crate::protocol::packet::Packet;
 
 impl crate::Engine {
     // LongConn.OffPicUp
+    #[must_use]
     pub fn build_off_pic_up_packet(
         &self,
         target: i64,
@@ -33,6 +35,6 @@ impl crate::Engine {
             }],
             ..Default::default()
         };
-        self.uni_packet("LongConn.OffPicUp", req.to_bytes())
+        self.uni_packet(commands::LONG_CONN_OFF_PIC_UP, req.to_bytes())
     }
 }
@@ -9,8 +9,7 @@ use super::OffPicUpResp;
 impl crate::Engine {
     // LongConn.OffPicUp
     pub fn decode_off_pic_up_response(&self, payload: Bytes) -> RQResult<OffPicUpResp> {
-        let mut resp = pb::cmd0x352::RspBody::from_bytes(&payload)
-            .map_err(|_| RQError::Decode("RspBody".into()))?;
+        let mut resp = pb::cmd0x352::RspBody::from_bytes(&payload)?;
         if let Some(err) = resp.fail_msg {
             return Err(RQError::Other(String::from_utf8_lossy(&err).to_string()));
         }
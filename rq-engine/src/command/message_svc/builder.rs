@@ -1,12 +1,14 @@
 use prost::Message;
 
 use crate::command::common::PbToBytes;
+use crate::commands;
 use crate::pb;
 use crate::protocol::packet::Packet;
 
 // TODO 待测试
 impl super::super::super::Engine {
     // MessageSvc.PbSendMsg
+    #[must_use]
     pub fn build_group_sending_packet(
         &self,
         group_code: i64,
@@ -52,7 +54,7 @@ impl super::super::super::Engine {
             },
             ..Default::default()
         };
-        self.uni_packet("MessageSvc.PbSendMsg", req.to_bytes())
+        self.uni_packet(commands::MESSAGE_SVC_PB_SEND_MSG, req.to_bytes())
     }
 
     // build sync_cookie
@@ -75,6 +77,7 @@ impl super::super::super::Engine {
     }
 
     // MessageSvc.PbGetMsg
+    #[must_use]
     pub fn build_get_message_request_packet(&self, flag: i32, time: i64) -> Packet {
         // start = 0, continue = 1, stop = 2
         let sync_cookie = self.sync_cookie(time);
@@ -91,16 +94,18 @@ impl super::super::super::Engine {
             server_buf: Some(vec![]),
             ..Default::default()
         };
-        self.uni_packet("MessageSvc.PbGetMsg", req.to_bytes())
+        self.uni_packet(commands::MESSAGE_SVC_PB_GET_MSG, req.to_bytes())
     }
 
     // MessageSvc.PbDeleteMsg
+    #[must_use]
     pub fn build_delete_message_request_packet(&self, items: Vec<pb::MessageItem>) -> Packet {
         let body = pb::DeleteMessageRequest { items }.to_bytes();
-        self.uni_packet("MessageSvc.PbDeleteMsg", body)
+        self.uni_packet(commands::MESSAGE_SVC_PB_DELETE_MSG, body)
     }
 
     // MessageSvc.PbSendMsg
+    #[must_use]
     pub fn build_friend_sending_packet(
         &self,
         target: i64,
@@ -141,10 +146,11 @@ impl super::super::super::Engine {
             sync_cookie: Some(sync_cookie),
             ..Default::default()
         };
-        self.uni_packet("MessageSvc.PbSendMsg", req.to_bytes())
+        self.uni_packet(commands::MESSAGE_SVC_PB_SEND_MSG, req.to_bytes())
     }
 
     // MessageSvc.PbSendMsg
+    #[must_use]
     pub fn build_temp_sending_packet(
         &self,
         group_uin: i64,
@@ -179,10 +185,11 @@ impl super::super::super::Engine {
             sync_cookie: Some(sync_cookie),
             ..Default::default()
         };
-        self.uni_packet("MessageSvc.PbSendMsg", req.to_bytes())
+        self.uni_packet(commands::MESSAGE_SVC_PB_SEND_MSG, req.to_bytes())
     }
 
     // MessageSvc.PbGetGroupMsg
+    #[must_use]
     pub fn build_get_group_msg_request(
         &self,
         group_code: i64,
@@ -196,9 +203,10 @@ impl super::super::super::Engine {
             public_group: Some(false),
             ..Default::default()
         };
-        self.uni_packet("MessageSvc.PbGetGroupMsg", req.to_bytes())
+        self.uni_packet(commands::MESSAGE_SVC_PB_GET_GROUP_MSG, req.to_bytes())
     }
 
+    #[must_use]
     pub fn build_private_recall_packet(
         &self,
         uin: i64,
@@ -231,9 +239,10 @@ impl super::super::super::Engine {
             }],
             ..Default::default()
         };
-        self.uni_packet("PbMessageSvc.PbMsgWithDraw", req.to_bytes())
+        self.uni_packet(commands::PB_MESSAGE_SVC_PB_MSG_WITH_DRAW, req.to_bytes())
     }
 
+    #[must_use]
     pub fn build_group_recall_packet(
         &self,
         group_code: i64,
@@ -258,6 +267,6 @@ impl super::super::super::Engine {
             }],
             ..Default::default()
         };
-        self.uni_packet("PbMessageSvc.PbMsgWithDraw", req.to_bytes())
+        self.uni_packet(commands::PB_MESSAGE_SVC_PB_MSG_WITH_DRAW, req.to_bytes())
     }
 }
@@ -1,7 +1,7 @@
 use bytes::{Buf, Bytes};
 
 use crate::command::common::PbToBytes;
-use crate::pb::msg::GetMessageResponse;
+use crate::pb::msg::{GetMessageResponse, SendMessageResponse};
 use crate::{jce, RQError, RQResult};
 
 impl crate::Engine {
@@ -44,11 +44,10 @@ impl crate::Engine {
         &self,
         payload: Bytes,
     ) -> RQResult<super::MessageSyncResponse> {
-        let resp = GetMessageResponse::from_bytes(&payload)
-            .map_err(|_| RQError::Decode("GetMessageResponse".to_string()))?;
+        let resp = GetMessageResponse::from_bytes(&payload)?;
         Ok(super::MessageSyncResponse {
             msg_rsp_type: resp.msg_rsp_type.unwrap_or_default(),
-            sync_flag: resp.sync_flag.unwrap(),
+            sync_flag: resp.sync_flag.unwrap_or_default(),
             sync_cookie: resp.sync_cookie,
             pub_account_cookie: resp.pub_account_cookie,
             msgs: resp
@@ -58,4 +57,13 @@ impl crate::Engine {
                 .collect(),
         })
     }
+
+    // MessageSvc.PbSendMsg 返回服务器时间
+    pub fn decode_send_message_response(&self, payload: Bytes) -> RQResult<i64> {
+        let resp = SendMessageResponse::from_bytes(&payload)?;
+        match resp.result.unwrap_or_default() {
+            0 => Ok(resp.send_time.unwrap_or_default() as i64),
+            result => Err(RQError::UnsuccessfulRetCode(result)),
+        }
+    }
 }
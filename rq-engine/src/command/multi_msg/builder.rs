@@ -0,0 +1,112 @@
+use std::io::Write;
+
+use bytes::Bytes;
+use flate2::{write::GzEncoder, Compression};
+
+use crate::command::common::PbToBytes;
+use crate::commands;
+use crate::pb;
+use crate::protocol::packet::Packet;
+use crate::RQResult;
+
+impl super::super::super::Engine {
+    // MultiMsg.ApplyUp，data_len 和 data_md5 为 gzip 压缩后的消息内容
+    #[must_use]
+    pub fn build_multi_apply_up_packet(
+        &self,
+        dst_uin: i64,
+        data_len: i64,
+        data_md5: Vec<u8>,
+        bu_type: i32,
+    ) -> Packet {
+        let req = pb::multimsg::MultiReqBody {
+            subcmd: Some(1),
+            term_type: Some(5),
+            platform_type: Some(9),
+            net_type: Some(3),
+            build_ver: Some(self.transport.version.build_ver.into()),
+            multimsg_applyup_req: vec![pb::multimsg::MultiMsgApplyUpReq {
+                dst_uin: Some(dst_uin),
+                msg_size: Some(data_len),
+                msg_md5: Some(data_md5),
+                msg_type: Some(3),
+                ..Default::default()
+            }],
+            bu_type: Some(bu_type),
+            ..Default::default()
+        };
+        self.uni_packet(commands::MULTI_MSG_APPLY_UP, req.to_bytes())
+    }
+
+    /// 把群消息打包成 PbMultiMsgTransmit 并 gzip 压缩，用于长消息上传
+    pub fn pack_group_long_message(
+        &self,
+        group_code: i64,
+        group_card: String,
+        elems: Vec<pb::msg::Elem>,
+        time: i64,
+    ) -> RQResult<Bytes> {
+        let msg = vec![pb::msg::Message {
+            head: Some(pb::msg::MessageHead {
+                from_uin: Some(self.uin()),
+                msg_seq: Some(self.next_group_seq()),
+                msg_time: Some(time as i32),
+                msg_uid: Some(0x0100_0000_0000_0000 | (rand::random::<u32>() as i64)),
+                mutiltrans_head: Some(pb::msg::MutilTransHead {
+                    msg_id: Some(1),
+                    ..Default::default()
+                }),
+                msg_type: Some(82),
+                group_info: Some(pb::msg::GroupInfo {
+                    group_code: Some(group_code),
+                    group_card: Some(group_card),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            body: Some(pb::msg::MessageBody {
+                rich_text: Some(pb::msg::RichText {
+                    elems,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }];
+        let transmit = pb::msg::PbMultiMsgTransmit {
+            msg: msg.clone(),
+            pb_item_list: vec![pb::msg::PbMultiMsgItem {
+                file_name: Some("MultiMsg".into()),
+                buffer: Some(pb::msg::PbMultiMsgNew { msg }),
+            }],
+        };
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&transmit.to_bytes())?;
+        Ok(encoder.finish()?.into())
+    }
+
+    /// 长消息通过 highway 上传的内容，content 为 pack_group_long_message 的结果
+    pub fn build_long_req_body(
+        &self,
+        dst_uin: i64,
+        content: Vec<u8>,
+        upload_key: Vec<u8>,
+    ) -> Vec<u8> {
+        pb::longmsg::LongReqBody {
+            subcmd: Some(1),
+            term_type: Some(5),
+            platform_type: Some(9),
+            msg_up_req: vec![pb::longmsg::LongMsgUpReq {
+                msg_type: Some(3),
+                dst_uin: Some(dst_uin),
+                msg_content: Some(content),
+                store_type: Some(2),
+                msg_ukey: Some(upload_key),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+        .to_bytes()
+        .to_vec()
+    }
+}
@@ -0,0 +1,33 @@
+use std::net::{Ipv4Addr, SocketAddr};
+
+use bytes::Bytes;
+
+use crate::command::common::PbToBytes;
+use crate::command::multi_msg::MultiMsgApplyUpResp;
+use crate::common::RQIP;
+use crate::{pb, RQError, RQResult};
+
+impl super::super::super::Engine {
+    // MultiMsg.ApplyUp
+    pub fn decode_multi_apply_up_response(&self, payload: Bytes) -> RQResult<MultiMsgApplyUpResp> {
+        let mut rsp = pb::multimsg::MultiRspBody::from_bytes(&payload)?;
+        let rsp = rsp
+            .multimsg_applyup_rsp
+            .pop()
+            .ok_or_else(|| RQError::Other("multimsg_applyup_rsp.len = 0".into()))?;
+        if rsp.result() != 0 {
+            return Err(RQError::UnsuccessfulRetCode(rsp.result()));
+        }
+        Ok(MultiMsgApplyUpResp {
+            res_id: rsp.msg_resid.unwrap_or_default(),
+            upload_key: rsp.msg_ukey.unwrap_or_default(),
+            msg_sig: rsp.msg_sig.unwrap_or_default(),
+            upload_addrs: rsp
+                .uint32_up_ip
+                .into_iter()
+                .zip(rsp.uint32_up_port)
+                .map(|(ip, port)| SocketAddr::new(Ipv4Addr::from(RQIP(ip)).into(), port as u16))
+                .collect(),
+        })
+    }
+}
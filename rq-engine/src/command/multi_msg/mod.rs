@@ -0,0 +1,12 @@
+use std::net::SocketAddr;
+
+pub mod builder;
+pub mod decoder;
+
+#[derive(Debug, Clone)]
+pub struct MultiMsgApplyUpResp {
+    pub res_id: String,
+    pub upload_key: Vec<u8>,
+    pub msg_sig: Vec<u8>,
+    pub upload_addrs: Vec<SocketAddr>,
+}
@@ -3,11 +3,13 @@ use bytes::{BufMut, BytesMut};
 use crate::command::common::PbToBytes;
 use crate::command::oidb_svc::music::{MusicShare, MusicVersion, SendMusicTarget};
 use crate::command::oidb_svc::ProfileDetailUpdate;
+use crate::commands;
 use crate::pb;
 use crate::protocol::packet::Packet;
 
 impl super::super::super::Engine {
     // OidbSvc.0x4ff_9_IMCore
+    #[must_use]
     pub fn build_update_profile_detail_packet(&self, profile: ProfileDetailUpdate) -> Packet {
         let mut w = BytesMut::new();
         w.put_u32(self.uin() as u32);
@@ -19,10 +21,11 @@ impl super::super::super::Engine {
             w.put_slice(&value);
         }
         let payload = self.transport.encode_oidb_packet(0x4ff, 9, w.freeze());
-        self.uni_packet("OidbSvc.0x4ff_9_IMCore", payload)
+        self.uni_packet(commands::OIDB_SVC_0X4FF_9_IMCORE, payload)
     }
 
     // OidbSvc.0x88d_0
+    #[must_use]
     pub fn build_group_info_request_packet(&self, group_codes: Vec<i64>) -> Packet {
         let body = pb::oidb::D88dReqBody {
             app_id: Some(self.transport.version.app_id),
@@ -64,10 +67,52 @@ impl super::super::super::Engine {
             pc_client_version: Some(0),
         };
         let payload = self.transport.encode_oidb_packet(0x88d, 0, body.to_bytes());
-        self.uni_packet("OidbSvc.0x88d_0", payload)
+        self.uni_packet(commands::OIDB_SVC_0X88D_0, payload)
+    }
+
+    // OidbSvc.0x88d_0 只查询群人数
+    #[must_use]
+    pub fn build_group_member_count_request_packet(&self, group_code: i64) -> Packet {
+        let body = pb::oidb::D88dReqBody {
+            app_id: Some(self.transport.version.app_id),
+            req_group_info: vec![pb::oidb::ReqGroupInfo {
+                group_code: Some(group_code as u64),
+                stgroupinfo: Some(pb::oidb::D88dGroupInfo {
+                    group_member_max_num: Some(0),
+                    group_member_num: Some(0),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            pc_client_version: Some(0),
+        };
+        let payload = self.transport.encode_oidb_packet(0x88d, 0, body.to_bytes());
+        self.uni_packet(commands::OIDB_SVC_0X88D_0, payload)
+    }
+
+    // OidbSvc.0x88d_0 只查询入群问题
+    #[must_use]
+    pub fn build_group_entrance_question_request_packet(&self, group_code: i64) -> Packet {
+        let body = pb::oidb::D88dReqBody {
+            app_id: Some(self.transport.version.app_id),
+            req_group_info: vec![pb::oidb::ReqGroupInfo {
+                group_code: Some(group_code as u64),
+                stgroupinfo: Some(pb::oidb::D88dGroupInfo {
+                    group_option: Some(0),
+                    group_question: Some(vec![]),
+                    group_answer: Some(vec![]),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            pc_client_version: Some(0),
+        };
+        let payload = self.transport.encode_oidb_packet(0x88d, 0, body.to_bytes());
+        self.uni_packet(commands::OIDB_SVC_0X88D_0, payload)
     }
 
     // OidbSvc.0x570_8
+    #[must_use]
     pub fn build_group_mute_packet(
         &self,
         group_code: i64,
@@ -81,16 +126,33 @@ impl super::super::super::Engine {
         w.put_u32(member_uin as u32);
         w.put_u32(duration);
         let payload = self.transport.encode_oidb_packet(0x570, 8, w.freeze());
-        self.uni_packet("OidbSvc.0x570_8", payload)
+        self.uni_packet(commands::OIDB_SVC_0X570_8, payload)
+    }
+
+    // OidbSvc.0x8ba_0
+    #[must_use]
+    pub fn build_group_transfer_packet(
+        &self,
+        group_code: i64,
+        old_owner_uin: i64,
+        new_owner_uin: i64,
+    ) -> Packet {
+        let mut w = BytesMut::new();
+        w.put_u32(group_code as u32);
+        w.put_u32(old_owner_uin as u32);
+        w.put_u32(new_owner_uin as u32);
+        let payload = self.transport.encode_oidb_packet(0x8ba, 0, w.freeze());
+        self.uni_packet(commands::OIDB_SVC_0X8BA_0, payload)
     }
 
     // OidbSvc.0x89a_0
     fn build_group_operation_packet(&self, body: pb::oidb::D89aReqBody) -> Packet {
         let payload = self.transport.encode_oidb_packet(0x89a, 0, body.to_bytes());
-        self.uni_packet("OidbSvc.0x89a_0", payload)
+        self.uni_packet(commands::OIDB_SVC_0X89A_0, payload)
     }
 
     // OidbSvc.0x89a_0
+    #[must_use]
     pub fn build_group_mute_all_packet(&self, group_code: i64, mute: bool) -> Packet {
         let shut_up_time: i32 = if mute { 268435455 } else { 0 };
         let body = pb::oidb::D89aReqBody {
@@ -105,6 +167,7 @@ impl super::super::super::Engine {
     }
 
     // OidbSvc.0x89a_0
+    #[must_use]
     pub fn build_group_name_update_packet(&self, group_code: i64, name: String) -> Packet {
         let body = pb::oidb::D89aReqBody {
             group_code,
@@ -118,6 +181,7 @@ impl super::super::super::Engine {
     }
 
     // OidbSvc.0x89a_0
+    #[must_use]
     pub fn build_group_memo_update_packet(&self, group_code: i64, memo: String) -> Packet {
         let body = pb::oidb::D89aReqBody {
             group_code,
@@ -130,7 +194,44 @@ impl super::super::super::Engine {
         self.build_group_operation_packet(body)
     }
 
+    // OidbSvc.0x89a_0
+    #[must_use]
+    pub fn build_group_notice_update_packet(&self, group_code: i64, notice: String) -> Packet {
+        let body = pb::oidb::D89aReqBody {
+            group_code,
+            st_group_info: Some(pb::oidb::D89aGroupinfo {
+                ing_group_finger_memo: notice.as_bytes().to_vec(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        self.build_group_operation_packet(body)
+    }
+
+    // OidbSvc.0x89a_0
+    // add_option 4: 回答问题并由管理员审核, 5: 正确回答问题
+    #[must_use]
+    pub fn build_group_entrance_question_update_packet(
+        &self,
+        group_code: i64,
+        question: String,
+        answer: String,
+    ) -> Packet {
+        let body = pb::oidb::D89aReqBody {
+            group_code,
+            st_group_info: Some(pb::oidb::D89aGroupinfo {
+                add_option: if answer.is_empty() { 4 } else { 5 },
+                ing_group_question: question.into_bytes(),
+                ing_group_answer: answer.into_bytes(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        self.build_group_operation_packet(body)
+    }
+
     // OidbSvc.0x8a0_0
+    #[must_use]
     pub fn build_group_kick_packet(
         &self,
         group_code: i64,
@@ -154,10 +255,11 @@ impl super::super::super::Engine {
             ..Default::default()
         };
         let payload = self.transport.encode_oidb_packet(0x8a0, 0, body.to_bytes());
-        self.uni_packet("OidbSvc.0x8a0_0", payload)
+        self.uni_packet(commands::OIDB_SVC_0X8A0_0, payload)
     }
 
     // OidbSvc.0xed3
+    #[must_use]
     pub fn build_group_poke_packet(&self, group_code: i64, target: i64) -> Packet {
         let body = pb::oidb::Ded3ReqBody {
             to_uin: target,
@@ -165,10 +267,11 @@ impl super::super::super::Engine {
             ..Default::default()
         };
         let payload = self.transport.encode_oidb_packet(0xed3, 1, body.to_bytes());
-        self.uni_packet("OidbSvc.0xed3", payload)
+        self.uni_packet(commands::OIDB_SVC_0XED3, payload)
     }
 
     // OidbSvc.0xed3
+    #[must_use]
     pub fn build_friend_poke_packet(&self, target: i64) -> Packet {
         let body = pb::oidb::Ded3ReqBody {
             to_uin: target,
@@ -176,20 +279,22 @@ impl super::super::super::Engine {
             ..Default::default()
         };
         let payload = self.transport.encode_oidb_packet(0xed3, 1, body.to_bytes());
-        self.uni_packet("OidbSvc.0xed3", payload)
+        self.uni_packet(commands::OIDB_SVC_0XED3, payload)
     }
 
     // OidbSvc.0x55c_1
+    #[must_use]
     pub fn build_group_admin_set_packet(&self, group_code: i64, member: i64, flag: bool) -> Packet {
         let mut w = BytesMut::new();
         w.put_u32(group_code as u32);
         w.put_u32(member as u32);
         w.put_u8(if flag { 0x01 } else { 0x00 });
         let payload = self.transport.encode_oidb_packet(0x55c, 1, w.freeze());
-        self.uni_packet("OidbSvc.0x55c_1", payload)
+        self.uni_packet(commands::OIDB_SVC_0X55C_1, payload)
     }
 
     // OidbSvc.0x758
+    #[must_use]
     pub fn build_group_invite_packet(&self, group_code: i64, uin: i64) -> Packet {
         let body = pb::oidb::D758ReqBody {
             join_group_code: Some(group_code as u64),
@@ -200,10 +305,11 @@ impl super::super::super::Engine {
             ..Default::default()
         };
         let payload = self.transport.encode_oidb_packet(0x758, 1, body.to_bytes());
-        self.uni_packet("OidbSvc.0x758", payload)
+        self.uni_packet(commands::OIDB_SVC_0X758, payload)
     }
 
     // OidbSvc.0x8a7_0
+    #[must_use]
     pub fn build_group_at_all_remain_request_packet(&self, group_code: i64) -> Packet {
         let body = pb::oidb::D8a7ReqBody {
             sub_cmd: Some(1),
@@ -213,10 +319,11 @@ impl super::super::super::Engine {
             group_code: Some(group_code as u64),
         };
         let payload = self.transport.encode_oidb_packet(0x8a7, 0, body.to_bytes());
-        self.uni_packet("OidbSvc.0x8a7_0", payload)
+        self.uni_packet(commands::OIDB_SVC_0X8A7_0, payload)
     }
 
     // OidbSvc.0x8fc_2
+    #[must_use]
     pub fn build_edit_special_title_packet(
         &self,
         group_code: i64,
@@ -235,10 +342,11 @@ impl super::super::super::Engine {
             ..Default::default()
         };
         let payload = self.transport.encode_oidb_packet(0x8fc, 2, body.to_bytes());
-        self.uni_packet("OidbSvc.0x8fc_2", payload)
+        self.uni_packet(commands::OIDB_SVC_0X8FC_2, payload)
     }
 
     // OidbSvc.0x990
+    #[must_use]
     pub fn build_translate_request_packet(
         &self,
         src_language: String,
@@ -253,10 +361,72 @@ impl super::super::super::Engine {
             }),
         };
         let payload = self.transport.encode_oidb_packet(0x990, 2, body.to_bytes());
-        self.uni_packet("OidbSvc.0x990", payload)
+        self.uni_packet(commands::OIDB_SVC_0X990, payload)
+    }
+
+    // OidbSvc.0x6d8_1 群文件列表，folder_id 为 "/" 时是根目录
+    #[must_use]
+    pub fn build_group_file_list_request_packet(
+        &self,
+        group_code: i64,
+        folder_id: String,
+        start_index: u32,
+    ) -> Packet {
+        let body = pb::oidb::D6d8ReqBody {
+            file_list_info_req: Some(pb::oidb::GetFileListReqBody {
+                group_code: group_code as u64,
+                app_id: 3,
+                folder_id,
+                file_count: 20,
+                req_from: 3,
+                sort_by: 1,
+                start_index,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let payload = self.transport.encode_oidb_packet(0x6d8, 1, body.to_bytes());
+        self.uni_packet(commands::OIDB_SVC_0X6D8_1, payload)
+    }
+
+    // OidbSvc.0x6d8_1 群文件数量
+    #[must_use]
+    pub fn build_group_file_count_request_packet(&self, group_code: i64) -> Packet {
+        let body = pb::oidb::D6d8ReqBody {
+            group_file_count_req: Some(pb::oidb::GetFileCountReqBody {
+                group_code: group_code as u64,
+                app_id: 3,
+                bus_id: 0,
+            }),
+            ..Default::default()
+        };
+        let payload = self.transport.encode_oidb_packet(0x6d8, 2, body.to_bytes());
+        self.uni_packet(commands::OIDB_SVC_0X6D8_1, payload)
+    }
+
+    // OidbSvc.0x6d6_2 群文件下载链接
+    #[must_use]
+    pub fn build_group_file_download_request_packet(
+        &self,
+        group_code: i64,
+        file_id: String,
+        bus_id: u32,
+    ) -> Packet {
+        let body = pb::oidb::D6d6ReqBody {
+            download_file_req: Some(pb::oidb::DownloadFileReqBody {
+                group_code: group_code as u64,
+                app_id: 3,
+                bus_id,
+                file_id,
+                ..Default::default()
+            }),
+        };
+        let payload = self.transport.encode_oidb_packet(0x6d6, 2, body.to_bytes());
+        self.uni_packet(commands::OIDB_SVC_0X6D6_2, payload)
     }
 
     // OidbSvc.0xeac
+    #[must_use]
     pub fn build_essence_msg_operate_packet(
         &self,
         group_code: i64,
@@ -272,16 +442,17 @@ impl super::super::super::Engine {
         let payload =
             self.transport
                 .encode_oidb_packet(0xeac, if flag { 1 } else { 2 }, body.to_bytes());
-        self.uni_packet("OidbSvc.0xeac", payload)
+        self.uni_packet(commands::OIDB_SVC_0XEAC, payload)
     }
 
     // OidbSvc.0xe07_0
+    #[must_use]
     pub fn build_image_ocr_request_packet(
         &self,
         url: String,
         md5: String,
         size: i32,
-        wight: i32,
+        width: i32,
         height: i32,
     ) -> Packet {
         let body = pb::oidb::De07ReqBody {
@@ -293,15 +464,16 @@ impl super::super::super::Engine {
                 after_compress_md5: md5,
                 after_compress_file_size: size,
                 after_compress_height: height,
-                after_compress_weight: wight,
+                after_compress_weight: width,
                 ..Default::default()
             }),
             ..Default::default()
         };
         let payload = self.transport.encode_oidb_packet(0xe07, 0, body.to_bytes());
-        self.uni_packet("OidbSvc.0xe07_0", payload)
+        self.uni_packet(commands::OIDB_SVC_0XE07_0, payload)
     }
 
+    #[must_use]
     pub fn build_share_music_request_packet(
         &self,
         send_music_target: SendMusicTarget,
@@ -346,6 +518,6 @@ impl super::super::super::Engine {
             ..Default::default()
         };
         let payload = self.transport.encode_oidb_packet(0xb77, 9, body.to_bytes());
-        self.uni_packet("OidbSvc.0xb77_9", payload)
+        self.uni_packet(commands::OIDB_SVC_0XB77_9, payload)
     }
 }
@@ -1,20 +1,23 @@
 use bytes::Bytes;
 
 use crate::command::common::PbToBytes;
-use crate::command::oidb_svc::GroupAtAllRemainInfo;
+use crate::command::oidb_svc::{
+    EntranceQuestion, GroupAtAllRemainInfo, GroupFile, GroupFileCount, GroupFileList, GroupFolder,
+};
+use crate::hex::encode_hex;
 use crate::structs::GroupInfo;
 use crate::{pb, RQError, RQResult};
 
 use super::OcrResponse;
 
+// 翻译额度用完时 BatchTranslateRsp 的 error_code
+pub(crate) const TRANSLATE_RATE_LIMITED_CODE: i32 = 1002;
+
 impl super::super::super::Engine {
     // OidbSvc.0x88d_0
     pub fn decode_group_info_response(&self, payload: Bytes) -> RQResult<Vec<GroupInfo>> {
-        let pkg = pb::oidb::OidbssoPkg::from_bytes(&payload)
-            .map_err(|_| RQError::Decode("OidbssoPkg".into()))?;
-        let groups = pb::oidb::D88dRspBody::from_bytes(&pkg.bodybuffer)
-            .map(|rsp| rsp.rsp_group_info)
-            .map_err(|_| RQError::Decode("D8a7RspBody".into()))?;
+        let pkg = pb::oidb::OidbssoPkg::from_bytes(&payload)?;
+        let groups = pb::oidb::D88dRspBody::from_bytes(&pkg.bodybuffer)?.rsp_group_info;
         Ok(groups
             .into_iter()
             .filter_map(|g| {
@@ -25,6 +28,8 @@ impl super::super::super::Engine {
                     code,
                     name: String::from_utf8_lossy(&info.group_name?).to_string(),
                     memo: String::from_utf8_lossy(&info.group_memo?).to_string(),
+                    notice: String::from_utf8_lossy(&info.group_finger_memo.unwrap_or_default())
+                        .to_string(),
                     owner_uin: info.group_owner? as i64,
                     group_create_time: info.group_create_time.unwrap_or_default(),
                     group_level: info.group_level.unwrap_or_default(),
@@ -38,46 +43,176 @@ impl super::super::super::Engine {
             .collect())
     }
 
+    // OidbSvc.0x88d_0 (当前人数, 最大人数)
+    pub fn decode_group_member_count_response(&self, payload: Bytes) -> RQResult<(u32, u32)> {
+        let pkg = pb::oidb::OidbssoPkg::from_bytes(&payload)?;
+        let info = pb::oidb::D88dRspBody::from_bytes(&pkg.bodybuffer)?
+            .rsp_group_info
+            .into_iter()
+            .next()
+            .and_then(|g| g.group_info)
+            .ok_or_else(|| RQError::Decode("group_info is none".into()))?;
+        Ok((
+            info.group_member_num.unwrap_or_default(),
+            info.group_member_max_num.unwrap_or_default(),
+        ))
+    }
+
+    // OidbSvc.0x88d_0
+    pub fn decode_group_entrance_question_response(
+        &self,
+        payload: Bytes,
+    ) -> RQResult<Option<EntranceQuestion>> {
+        let pkg = pb::oidb::OidbssoPkg::from_bytes(&payload)?;
+        let info = pb::oidb::D88dRspBody::from_bytes(&pkg.bodybuffer)?
+            .rsp_group_info
+            .into_iter()
+            .next()
+            .and_then(|g| g.group_info)
+            .ok_or_else(|| RQError::Decode("group_info is none".into()))?;
+        let question =
+            String::from_utf8_lossy(&info.group_question.unwrap_or_default()).to_string();
+        if question.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(EntranceQuestion {
+            question,
+            is_answer_required: info.group_option.unwrap_or_default() == 5,
+        }))
+    }
+
+    // OidbSvc.0x89a_0, OidbSvc.0x8ba_0 只需要检查 result
+    pub fn decode_group_operation_response(&self, payload: Bytes) -> RQResult<()> {
+        let pkg = pb::oidb::OidbssoPkg::from_bytes(&payload)?;
+        if pkg.result != 0 {
+            return Err(RQError::UnsuccessfulRetCode(pkg.result));
+        }
+        Ok(())
+    }
+
     // // OidbSvc.0x8a7_0
     pub fn decode_group_at_all_remain_response(
         &self,
         payload: Bytes,
     ) -> RQResult<GroupAtAllRemainInfo> {
-        let pkg = pb::oidb::OidbssoPkg::from_bytes(&payload)
-            .map_err(|_| RQError::Decode("OidbssoPkg".into()))?;
-        let rsp = pb::oidb::D8a7RspBody::from_bytes(&pkg.bodybuffer)
-            .map_err(|_| RQError::Decode("D8a7RspBody".into()))?;
+        let pkg = pb::oidb::OidbssoPkg::from_bytes(&payload)?;
+        let rsp = pb::oidb::D8a7RspBody::from_bytes(&pkg.bodybuffer)?;
         Ok(GroupAtAllRemainInfo {
             can_at_all: rsp.can_at_all(),
             remain_at_all_count_for_group: rsp.remain_at_all_count_for_group(),
             remain_at_all_count_for_uin: rsp.remain_at_all_count_for_uin(),
+            prompt: String::from_utf8_lossy(rsp.prompt_msg1()).to_string(),
         })
     }
 
     // OidbSvc.0x990
     pub fn decode_translate_response(&self, payload: Bytes) -> RQResult<Vec<String>> {
-        let pkg = pb::oidb::OidbssoPkg::from_bytes(&payload)
-            .map_err(|_| RQError::Decode("OidbssoPkg".into()))?;
-        let rsp = pb::oidb::TranslateRspBody::from_bytes(&pkg.bodybuffer)
-            .map_err(|_| RQError::Decode("TranslateRspBody".into()))?;
-        Ok(rsp.batch_translate_rsp.unwrap_or_default().dst_text_list)
+        let pkg = pb::oidb::OidbssoPkg::from_bytes(&payload)?;
+        let rsp = pb::oidb::TranslateRspBody::from_bytes(&pkg.bodybuffer)?
+            .batch_translate_rsp
+            .unwrap_or_default();
+        match rsp.error_code {
+            0 => {}
+            TRANSLATE_RATE_LIMITED_CODE => {
+                return Err(RQError::RateLimited(
+                    String::from_utf8_lossy(&rsp.error_msg).to_string(),
+                ))
+            }
+            code => return Err(RQError::UnsuccessfulRetCode(code)),
+        }
+        Ok(rsp.dst_text_list)
+    }
+
+    // OidbSvc.0x6d8_1
+    pub fn decode_group_file_list_response(
+        &self,
+        group_code: i64,
+        payload: Bytes,
+    ) -> RQResult<GroupFileList> {
+        let pkg = pb::oidb::OidbssoPkg::from_bytes(&payload)?;
+        let rsp = pb::oidb::D6d8RspBody::from_bytes(&pkg.bodybuffer)?
+            .file_list_info_rsp
+            .ok_or_else(|| RQError::Decode("file_list_info_rsp is none".into()))?;
+        if rsp.ret_code != 0 {
+            return Err(RQError::UnsuccessfulRetCode(rsp.ret_code));
+        }
+        let mut list = GroupFileList {
+            is_end: rsp.is_end,
+            next_index: rsp.next_index,
+            ..Default::default()
+        };
+        for item in rsp.item_list {
+            if let Some(f) = item.file_info {
+                list.files.push(GroupFile {
+                    group_code,
+                    file_id: f.file_id,
+                    file_name: f.file_name,
+                    bus_id: f.bus_id,
+                    file_size: f.file_size,
+                    upload_time: f.upload_time,
+                    dead_time: f.dead_time,
+                    download_times: f.download_times,
+                    uploader_uin: f.uploader_uin as i64,
+                    uploader_name: f.uploader_name,
+                });
+            }
+            if let Some(f) = item.folder_info {
+                list.folders.push(GroupFolder {
+                    group_code,
+                    folder_id: f.folder_id,
+                    folder_name: f.folder_name,
+                    create_time: f.create_time,
+                    creator_uin: f.create_uin as i64,
+                    creator_name: f.creator_name,
+                    total_file_count: f.total_file_count,
+                });
+            }
+        }
+        Ok(list)
+    }
+
+    // OidbSvc.0x6d8_1
+    pub fn decode_group_file_count_response(&self, payload: Bytes) -> RQResult<GroupFileCount> {
+        let pkg = pb::oidb::OidbssoPkg::from_bytes(&payload)?;
+        let rsp = pb::oidb::D6d8RspBody::from_bytes(&pkg.bodybuffer)?
+            .group_file_count_rsp
+            .ok_or_else(|| RQError::Decode("group_file_count_rsp is none".into()))?;
+        if rsp.ret_code != 0 {
+            return Err(RQError::UnsuccessfulRetCode(rsp.ret_code));
+        }
+        Ok(GroupFileCount {
+            file_count: rsp.all_file_count,
+            limit_count: rsp.limit_count,
+        })
+    }
+
+    // OidbSvc.0x6d6_2
+    pub fn decode_group_file_download_response(&self, payload: Bytes) -> RQResult<String> {
+        let pkg = pb::oidb::OidbssoPkg::from_bytes(&payload)?;
+        let rsp = pb::oidb::D6d6RspBody::from_bytes(&pkg.bodybuffer)?
+            .download_file_rsp
+            .ok_or_else(|| RQError::Decode("download_file_rsp is none".into()))?;
+        if rsp.ret_code != 0 {
+            return Err(RQError::UnsuccessfulRetCode(rsp.ret_code));
+        }
+        Ok(format!(
+            "http://{}/ftn_handler/{}/",
+            rsp.download_ip,
+            encode_hex(&rsp.download_url)
+        ))
     }
 
     // OidbSvc.0xeac_1/2
     pub fn decode_essence_msg_response(&self, payload: Bytes) -> RQResult<pb::oidb::EacRspBody> {
-        let pkg = pb::oidb::OidbssoPkg::from_bytes(&payload)
-            .map_err(|_| RQError::Decode("OidbssoPkg".into()))?;
-        let resp = pb::oidb::EacRspBody::from_bytes(&pkg.bodybuffer)
-            .map_err(|_| RQError::Decode("EacRspBody".into()))?;
+        let pkg = pb::oidb::OidbssoPkg::from_bytes(&payload)?;
+        let resp = pb::oidb::EacRspBody::from_bytes(&pkg.bodybuffer)?;
         Ok(resp)
     }
 
     // OidbSvc.0xe07_0
     pub fn decode_image_ocr_response(&self, payload: Bytes) -> RQResult<OcrResponse> {
-        let pkg = pb::oidb::OidbssoPkg::from_bytes(&payload)
-            .map_err(|_| RQError::Decode("OidbssoPkg".into()))?;
-        let resp = pb::oidb::De07RspBody::from_bytes(&pkg.bodybuffer)
-            .map_err(|_| RQError::Decode("De07RspBody".into()))?;
+        let pkg = pb::oidb::OidbssoPkg::from_bytes(&payload)?;
+        let resp = pb::oidb::De07RspBody::from_bytes(&pkg.bodybuffer)?;
         Ok(OcrResponse {
             texts: resp
                 .ocr_rsp_body
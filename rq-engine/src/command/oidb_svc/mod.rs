@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::pb;
+use crate::{pb, RQError, RQResult};
 
 pub mod builder;
 pub mod decoder;
@@ -12,6 +12,99 @@ pub struct GroupAtAllRemainInfo {
     pub can_at_all: bool,
     pub remain_at_all_count_for_group: u32,
     pub remain_at_all_count_for_uin: u32,
+    // 不能 @全体 时服务器给出的提示
+    pub prompt: String,
+}
+
+impl GroupAtAllRemainInfo {
+    /// 当前是否还能 @全体成员
+    pub fn is_available(&self) -> bool {
+        self.can_at_all
+            && self.remain_at_all_count_for_group > 0
+            && self.remain_at_all_count_for_uin > 0
+    }
+}
+
+// 入群问题
+#[derive(Default, Debug, Clone)]
+pub struct EntranceQuestion {
+    pub question: String,
+    // 是否需要正确回答才能入群
+    pub is_answer_required: bool,
+}
+
+// 翻译语言
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Language {
+    Chinese,
+    English,
+    Japanese,
+    Korean,
+    French,
+    Spanish,
+    German,
+    Russian,
+    Other(String),
+}
+
+impl Language {
+    pub fn code(&self) -> &str {
+        match self {
+            Language::Chinese => "zh",
+            Language::English => "en",
+            Language::Japanese => "jp",
+            Language::Korean => "kr",
+            Language::French => "fr",
+            Language::Spanish => "es",
+            Language::German => "de",
+            Language::Russian => "ru",
+            Language::Other(code) => code,
+        }
+    }
+}
+
+// 群文件
+#[derive(Default, Debug, Clone)]
+pub struct GroupFile {
+    pub group_code: i64,
+    pub file_id: String,
+    pub file_name: String,
+    pub bus_id: u32,
+    pub file_size: u64,
+    pub upload_time: u32,
+    // 过期时间，0 表示永久
+    pub dead_time: u32,
+    pub download_times: u32,
+    pub uploader_uin: i64,
+    pub uploader_name: String,
+}
+
+// 群文件夹
+#[derive(Default, Debug, Clone)]
+pub struct GroupFolder {
+    pub group_code: i64,
+    pub folder_id: String,
+    pub folder_name: String,
+    pub create_time: u32,
+    pub creator_uin: i64,
+    pub creator_name: String,
+    pub total_file_count: u32,
+}
+
+// 一页群文件列表，is_end 为 false 时用 next_index 继续获取
+#[derive(Default, Debug, Clone)]
+pub struct GroupFileList {
+    pub files: Vec<GroupFile>,
+    pub folders: Vec<GroupFolder>,
+    pub is_end: bool,
+    pub next_index: u32,
+}
+
+// 群文件数量
+#[derive(Default, Debug, Clone)]
+pub struct GroupFileCount {
+    pub file_count: u32,
+    pub limit_count: u32,
 }
 
 pub struct OcrResponse {
@@ -19,7 +112,17 @@ pub struct OcrResponse {
     pub language: String,
 }
 
-// 编辑个人资料
+// 性别
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gender {
+    Unknown = 0,
+    Male = 1,
+    Female = 2,
+}
+
+// 编辑个人资料，<tag, value>
+// 20002 昵称, 20009 性别, 20011 邮箱, 20019 个人说明, 20021 学校, 20027 职业,
+// 20031 生日, 20032 故乡省份, 20033 故乡城市, 20035 学校类型, 20036 血型, 24008 公司
 #[derive(Default, Debug)]
 pub struct ProfileDetailUpdate(pub HashMap<u16, Vec<u8>>);
 
@@ -42,4 +145,156 @@ impl ProfileDetailUpdate {
     pub fn college(&mut self, value: String) {
         self.0.insert(20021, value.into_bytes());
     }
+    pub fn gender(&mut self, value: Gender) {
+        self.0.insert(20009, vec![value as u8]);
+    }
+    pub fn birthday(&mut self, year: u16, month: u8, day: u8) {
+        let mut value = year.to_be_bytes().to_vec();
+        value.extend([month, day]);
+        self.0.insert(20031, value);
+    }
+    pub fn blood_type(&mut self, value: u8) {
+        self.0.insert(20036, vec![value]);
+    }
+    pub fn hometown_province(&mut self, value: String) {
+        self.0.insert(20032, value.into_bytes());
+    }
+    pub fn hometown_city(&mut self, value: String) {
+        self.0.insert(20033, value.into_bytes());
+    }
+    pub fn school_type(&mut self, value: u8) {
+        self.0.insert(20035, vec![value]);
+    }
+    pub fn profession(&mut self, value: String) {
+        self.0.insert(20027, value.into_bytes());
+    }
+
+    pub fn with_nickname(mut self, value: &str) -> Self {
+        self.name(value.into());
+        self
+    }
+    pub fn with_gender(mut self, value: Gender) -> Self {
+        self.gender(value);
+        self
+    }
+    pub fn with_birthday(mut self, year: u16, month: u8, day: u8) -> Self {
+        self.birthday(year, month, day);
+        self
+    }
+    pub fn with_blood_type(mut self, value: u8) -> Self {
+        self.blood_type(value);
+        self
+    }
+    pub fn with_hometown_province(mut self, value: &str) -> Self {
+        self.hometown_province(value.into());
+        self
+    }
+    pub fn with_hometown_city(mut self, value: &str) -> Self {
+        self.hometown_city(value.into());
+        self
+    }
+    pub fn with_school(mut self, value: &str) -> Self {
+        self.college(value.into());
+        self
+    }
+    pub fn with_school_type(mut self, value: u8) -> Self {
+        self.school_type(value);
+        self
+    }
+    pub fn with_profession(mut self, value: &str) -> Self {
+        self.profession(value.into());
+        self
+    }
+    pub fn with_personal_note(mut self, value: &str) -> Self {
+        self.personal_note(value.into());
+        self
+    }
+
+    /// 发送前校验性别和生日
+    pub fn validate(&self) -> RQResult<()> {
+        if let Some(gender) = self.0.get(&20009) {
+            if !matches!(gender.as_slice(), [0..=2]) {
+                return Err(RQError::InvalidArgument(format!(
+                    "invalid gender {:?}, expected 0, 1 or 2",
+                    gender
+                )));
+            }
+        }
+        if let Some(birthday) = self.0.get(&20031) {
+            let valid = match birthday.as_slice() {
+                [y1, y2, month, day] => chrono::NaiveDate::from_ymd_opt(
+                    u16::from_be_bytes([*y1, *y2]) as i32,
+                    *month as u32,
+                    *day as u32,
+                )
+                .is_some(),
+                _ => false,
+            };
+            if !valid {
+                return Err(RQError::InvalidArgument(format!(
+                    "invalid birthday {:?}",
+                    birthday
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prost::Message;
+
+    use super::*;
+
+    #[test]
+    fn test_validate_profile_detail() {
+        assert!(ProfileDetailUpdate::new()
+            .with_gender(Gender::Female)
+            .with_birthday(2000, 2, 29)
+            .validate()
+            .is_ok());
+        assert!(ProfileDetailUpdate::new()
+            .with_birthday(2001, 2, 30)
+            .validate()
+            .is_err());
+        let mut profile = ProfileDetailUpdate::new();
+        profile.0.insert(20009, vec![3]);
+        assert!(profile.validate().is_err());
+    }
+
+    #[test]
+    fn test_decode_translate_rate_limited() {
+        let translate_rsp = |error_code: i32| {
+            let body = pb::oidb::TranslateRspBody {
+                batch_translate_rsp: Some(pb::oidb::BatchTranslateRsp {
+                    error_code,
+                    dst_text_list: vec!["hello".into()],
+                    ..Default::default()
+                }),
+            };
+            pb::oidb::OidbssoPkg {
+                bodybuffer: body.encode_to_vec(),
+                ..Default::default()
+            }
+            .encode_to_vec()
+            .into()
+        };
+        let engine = crate::Engine::new(
+            crate::protocol::device::Device::random(),
+            crate::protocol::version::ANDROID_WATCH,
+        );
+        assert_eq!(
+            engine.decode_translate_response(translate_rsp(0)).unwrap(),
+            vec!["hello".to_string()]
+        );
+        assert!(matches!(
+            engine.decode_translate_response(translate_rsp(decoder::TRANSLATE_RATE_LIMITED_CODE)),
+            Err(RQError::RateLimited(_))
+        ));
+        assert!(matches!(
+            engine.decode_translate_response(translate_rsp(1)),
+            Err(RQError::UnsuccessfulRetCode(1))
+        ));
+    }
 }
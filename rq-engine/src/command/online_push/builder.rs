@@ -4,11 +4,13 @@ use bytes::Bytes;
 use jcers::JcePut;
 
 use crate::command::common::pack_uni_request_data;
+use crate::commands;
 use crate::jce;
 use crate::protocol::packet::Packet;
 
 impl super::super::super::Engine {
     // OnlinePush.RespPush
+    #[must_use]
     pub fn build_delete_online_push_packet(
         &self,
         uin: i64,
@@ -45,10 +47,11 @@ impl super::super::super::Engine {
             s_buffer: buf.freeze(),
             ..Default::default()
         };
-        self.uni_packet("OnlinePush.RespPush", pkt.freeze())
+        self.uni_packet(commands::ONLINE_PUSH_RESP_PUSH, pkt.freeze())
     }
 
+    #[must_use]
     pub fn build_sid_ticket_expired_response(&self, seq: i32) -> Packet {
-        self.uni_packet_with_seq(seq, "OnlinePush.SidTicketExpired", Bytes::new())
+        self.uni_packet_with_seq(seq, commands::ONLINE_PUSH_SID_TICKET_EXPIRED, Bytes::new())
     }
 }
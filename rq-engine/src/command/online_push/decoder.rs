@@ -4,15 +4,14 @@ use jcers::Jce;
 use crate::command::common::PbToBytes;
 use crate::command::online_push::{GroupMessagePart, OnlinePushTrans, PushTransInfo, ReqPush};
 use crate::common::group_uin2code;
-use crate::structs::{GroupLeave, GroupMemberPermission, MemberPermissionChange};
+use crate::structs::{GroupLeave, GroupMemberPermission, GroupOwnerChange, MemberPermissionChange};
 use crate::{jce, pb, RQError, RQResult};
 
 impl super::super::super::Engine {
     // 解析群消息分片 长消息需要合并
     // OnlinePush.PbPushGroupMsg
     pub fn decode_group_message_packet(&self, payload: Bytes) -> RQResult<GroupMessagePart> {
-        let message = pb::msg::PushMessagePacket::from_bytes(&payload)
-            .map_err(|_| RQError::Decode("PushMessagePacket".to_string()))?
+        let message = pb::msg::PushMessagePacket::from_bytes(&payload)?
             .message
             .ok_or_else(|| RQError::Decode("message is none".to_string()))?;
 
@@ -51,6 +50,11 @@ impl super::super::super::Engine {
             from_uin: head
                 .from_uin
                 .ok_or_else(|| RQError::Decode("from_uin is none".into()))?,
+            msg_uid: head.msg_uid.unwrap_or_default(),
+            group_card: head
+                .group_info
+                .as_ref()
+                .and_then(|info| info.group_card.clone()),
             elems: rich_text.elems.clone(),
             time: head
                 .msg_time
@@ -88,8 +92,7 @@ impl super::super::super::Engine {
     }
 
     pub fn decode_online_push_trans_packet(&self, payload: Bytes) -> RQResult<OnlinePushTrans> {
-        let info = pb::msg::TransMsgInfo::from_bytes(&payload)
-            .map_err(|_| RQError::Decode("failed to decode TransMsgInfo".to_string()))?;
+        let info = pb::msg::TransMsgInfo::from_bytes(&payload)?;
         let msg_seq = info.msg_seq.unwrap_or_default();
         let msg_uid = info.msg_uid.unwrap_or_default();
         let msg_time = info.msg_time.unwrap_or_default();
@@ -117,6 +120,7 @@ impl super::super::super::Engine {
                             info: PushTransInfo::MemberLeave(GroupLeave {
                                 group_code: group_uin2code(group_uin),
                                 member_uin: target,
+                                was_kicked: false,
                                 operator_uin: None,
                             }),
                         });
@@ -129,6 +133,7 @@ impl super::super::super::Engine {
                             info: PushTransInfo::MemberLeave(GroupLeave {
                                 group_code: group_uin2code(group_uin),
                                 member_uin: target,
+                                was_kicked: true,
                                 operator_uin: Some(operator),
                             }),
                         });
@@ -161,6 +166,19 @@ impl super::super::super::Engine {
                         }),
                     });
                 }
+                // 转让群主时 target 为原群主，var5 为新群主
+                if var5 != 0 {
+                    return Ok(OnlinePushTrans {
+                        msg_seq,
+                        msg_uid,
+                        msg_time,
+                        info: PushTransInfo::OwnerChange(GroupOwnerChange {
+                            group_code: group_uin2code(group_uin),
+                            old_owner_uin: target,
+                            new_owner_uin: var5,
+                        }),
+                    });
+                }
             }
             _ => {}
         }
@@ -171,6 +189,6 @@ impl super::super::super::Engine {
 
     // OnlinePush.PbC2CMsgSync
     pub fn decode_c2c_sync_packet(&self, payload: Bytes) -> RQResult<pb::msg::PbPushMsg> {
-        pb::msg::PbPushMsg::from_bytes(&payload).map_err(|_| RQError::Decode("PbPushMsg".into()))
+        Ok(pb::msg::PbPushMsg::from_bytes(&payload)?)
     }
 }
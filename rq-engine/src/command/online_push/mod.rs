@@ -1,4 +1,4 @@
-use crate::structs::{GroupLeave, MemberPermissionChange};
+use crate::structs::{GroupLeave, GroupOwnerChange, MemberPermissionChange};
 use crate::{jce, pb};
 
 pub mod builder;
@@ -14,7 +14,8 @@ pub struct ReqPush {
 pub enum PushTransInfo {
     MemberLeave(GroupLeave),
     MemberPermissionChange(MemberPermissionChange),
-    // TODO 转让
+    // 转让群主
+    OwnerChange(GroupOwnerChange),
 }
 #[derive(Debug, Clone)]
 pub struct OnlinePushTrans {
@@ -30,6 +31,10 @@ pub struct GroupMessagePart {
     pub rand: i32,
     pub group_code: i64,
     pub from_uin: i64,
+    // 重连后服务器可能重发，用于去重
+    pub msg_uid: i64,
+    // 发送者群名片
+    pub group_card: Option<String>,
     pub elems: Vec<pb::msg::Elem>,
     pub time: i32,
     // 语音消息
@@ -1,9 +1,11 @@
 use crate::command::common::PbToBytes;
+use crate::commands;
 use crate::pb;
 use crate::protocol::packet::Packet;
 
 impl super::super::super::Engine {
     // PbMessageSvc.PbMsgReadedReport
+    #[must_use]
     pub fn build_group_msg_readed_packet(&self, group_code: i64, msg_seq: i32) -> Packet {
         let req = pb::msg::PbMsgReadedReportReq {
             grp_read_report: vec![pb::msg::PbGroupReadedReportReq {
@@ -12,10 +14,14 @@ impl super::super::super::Engine {
             }],
             ..Default::default()
         };
-        self.uni_packet("PbMessageSvc.PbMsgReadedReport", req.to_bytes())
+        self.uni_packet(
+            commands::PB_MESSAGE_SVC_PB_MSG_READED_REPORT,
+            req.to_bytes(),
+        )
     }
 
     // PbMessageSvc.PbMsgReadedReport
+    #[must_use]
     pub fn build_private_msg_readed_packet(&self, uin: i64, time: i64) -> Packet {
         let transport = &self.transport;
         let req = pb::msg::PbMsgReadedReportReq {
@@ -29,6 +35,9 @@ impl super::super::super::Engine {
             }),
             ..Default::default()
         };
-        self.uni_packet("PbMessageSvc.PbMsgReadedReport", req.to_bytes())
+        self.uni_packet(
+            commands::PB_MESSAGE_SVC_PB_MSG_READED_REPORT,
+            req.to_bytes(),
+        )
     }
 }
@@ -1,11 +1,13 @@
 use jcers::JcePut;
 
 use crate::command::common::PbToBytes;
+use crate::commands;
 use crate::pb;
 use crate::protocol::packet::Packet;
 
 impl super::super::super::Engine {
     // ProfileService.Pb.ReqSystemMsgNew.Group
+    #[must_use]
     pub fn build_system_msg_new_group_packet(&self, suspicious: bool) -> Packet {
         let req = pb::structmsg::ReqSystemMsgNew {
             msg_num: 100,
@@ -35,10 +37,11 @@ impl super::super::super::Engine {
             ..Default::default()
         };
         let payload = req.to_bytes();
-        self.uni_packet("ProfileService.Pb.ReqSystemMsgNew.Group", payload)
+        self.uni_packet(commands::PROFILE_SERVICE_REQ_SYSTEM_MSG_NEW_GROUP, payload)
     }
 
     // ProfileService.Pb.ReqSystemMsgNew.Friend
+    #[must_use]
     pub fn build_system_msg_new_friend_packet(&self) -> Packet {
         let req = pb::structmsg::ReqSystemMsgNew {
             msg_num: 20,
@@ -56,10 +59,11 @@ impl super::super::super::Engine {
             ..Default::default()
         };
         let payload = req.to_bytes();
-        self.uni_packet("ProfileService.Pb.ReqSystemMsgNew.Friend", payload)
+        self.uni_packet(commands::PROFILE_SERVICE_REQ_SYSTEM_MSG_NEW_FRIEND, payload)
     }
 
     // ProfileService.Pb.ReqSystemMsgAction.Group
+    #[must_use]
     pub fn build_system_msg_group_action_packet(
         &self,
         msg_seq: i64,
@@ -90,10 +94,14 @@ impl super::super::super::Engine {
             language: 1000,
         };
         let payload = req.to_bytes();
-        self.uni_packet("ProfileService.Pb.ReqSystemMsgAction.Group", payload)
+        self.uni_packet(
+            commands::PROFILE_SERVICE_REQ_SYSTEM_MSG_ACTION_GROUP,
+            payload,
+        )
     }
 
     // ProfileService.Pb.ReqSystemMsgAction.Friend
+    #[must_use]
     pub fn build_system_msg_friend_action_packet(
         &self,
         req_id: i64,
@@ -116,10 +124,14 @@ impl super::super::super::Engine {
             ..Default::default()
         };
         let payload = req.to_bytes();
-        self.uni_packet("ProfileService.Pb.ReqSystemMsgAction.Friend", payload)
+        self.uni_packet(
+            commands::PROFILE_SERVICE_REQ_SYSTEM_MSG_ACTION_FRIEND,
+            payload,
+        )
     }
 
     // ProfileService.GroupMngReq
+    #[must_use]
     pub fn build_quit_group_packet(&self, group_code: i64) -> Packet {
         let mut jce_mut = jcers::JceMut::new();
         jce_mut.put_i32(2, 0);
@@ -148,6 +160,6 @@ impl super::super::super::Engine {
             s_buffer: buf.freeze(),
             ..Default::default()
         };
-        self.uni_packet("ProfileService.GroupMngReq", pkt.freeze())
+        self.uni_packet(commands::PROFILE_SERVICE_GROUP_MNG_REQ, pkt.freeze())
     }
 }
@@ -1,6 +1,7 @@
 use bytes::Bytes;
 
 use crate::command::common::PbToBytes;
+use crate::commands;
 use crate::hex::encode_hex;
 use crate::pb;
 use crate::protocol::packet::Packet;
@@ -75,6 +76,7 @@ impl super::super::super::Engine {
         req.to_bytes()
     }
 
+    #[must_use]
     pub fn build_group_ptt_down_req(&self, group_code: i64, file_md5: Vec<u8>) -> Packet {
         let req = pb::cmd0x388::D388ReqBody {
             net_type: Some(3),
@@ -95,9 +97,10 @@ impl super::super::super::Engine {
             }],
             ..Default::default()
         };
-        self.uni_packet("PttStore.GroupPttDown", req.to_bytes())
+        self.uni_packet(commands::PTT_STORE_GROUP_PTT_DOWN, req.to_bytes())
     }
 
+    #[must_use]
     pub fn build_c2c_ptt_down_req(&self, sender_uin: i64, file_uuid: Vec<u8>) -> Packet {
         let req = pb::cmd0x346::C346ReqBody {
             client_type: 104,
@@ -111,9 +114,6 @@ impl super::super::super::Engine {
             }),
             ..Default::default()
         };
-        self.uni_packet(
-            "PttCenterSvr.pb_pttCenter_CMD_REQ_APPLY_DOWNLOAD-1200",
-            req.to_bytes(),
-        )
+        self.uni_packet(commands::PTT_CENTER_SVR_APPLY_DOWNLOAD, req.to_bytes())
     }
 }
@@ -6,8 +6,7 @@ use crate::{pb, RQError};
 
 impl super::super::super::Engine {
     pub fn decode_group_try_up_ptt_resp(&self, payload: Bytes) -> RQResult<Vec<u8>> {
-        let mut rsp = pb::cmd0x388::D388RspBody::from_bytes(&payload)
-            .map_err(|_| RQError::Decode("D388RspBody".into()))?;
+        let mut rsp = pb::cmd0x388::D388RspBody::from_bytes(&payload)?;
         let ptt = rsp
             .tryup_ptt_rsp
             .pop()
@@ -17,16 +16,14 @@ impl super::super::super::Engine {
     }
 
     pub fn decode_private_try_up_ptt_resp(&self, payload: Bytes) -> RQResult<Vec<u8>> {
-        pb::cmd0x346::C346RspBody::from_bytes(&payload)
-            .map_err(|_| RQError::Decode("C346RspBody".into()))?
+        pb::cmd0x346::C346RspBody::from_bytes(&payload)?
             .apply_upload_rsp
             .map(|r| r.uuid)
             .ok_or_else(|| RQError::Other("apply_upload_rsp is none".into()))
     }
 
     pub fn decode_group_ptt_down(&self, payload: Bytes) -> RQResult<String> {
-        let mut rsp = pb::cmd0x388::D388RspBody::from_bytes(&payload)
-            .map_err(|_| RQError::Decode("D388RspBody".into()))?;
+        let mut rsp = pb::cmd0x388::D388RspBody::from_bytes(&payload)?;
         let ptt = rsp
             .getptt_url_rsp
             .pop()
@@ -43,8 +40,7 @@ impl super::super::super::Engine {
     }
 
     pub fn decode_c2c_ptt_down(&self, payload: Bytes) -> RQResult<String> {
-        pb::cmd0x346::C346RspBody::from_bytes(&payload)
-            .map_err(|_| RQError::Decode("C346RspBody".into()))?
+        pb::cmd0x346::C346RspBody::from_bytes(&payload)?
             .apply_download_rsp
             .ok_or_else(|| RQError::Other("apply_download_rsp is empty".into()))?
             .download_info
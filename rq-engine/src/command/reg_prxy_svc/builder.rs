@@ -6,11 +6,13 @@ use jcers::JcePut;
 
 use crate::command::common::pack_uni_request_data;
 use crate::command::common::PbToBytes;
+use crate::commands;
 use crate::protocol::packet::Packet;
 use crate::{jce, pb};
 
 impl super::super::super::Engine {
     // RegPrxySvc.getOffMsg
+    #[must_use]
     pub fn build_get_offline_msg_request_packet(&self, last_message_time: i64) -> Packet {
         let transport = &self.transport;
         let reg_req = jce::SvcReqRegisterNew {
@@ -73,10 +75,11 @@ impl super::super::super::Engine {
             s_buffer: buf.freeze(),
             ..Default::default()
         };
-        self.uni_packet("RegPrxySvc.getOffMsg", pkt.freeze())
+        self.uni_packet(commands::REG_PRXY_SVC_GET_OFF_MSG, pkt.freeze())
     }
 
     // RegPrxySvc.infoSync
+    #[must_use]
     pub fn build_sync_msg_request_packet(&self, last_message_time: i64) -> Packet {
         let transport = &self.transport;
         let oidb_req = pb::oidb::D769RspBody {
@@ -175,6 +178,6 @@ impl super::super::super::Engine {
             s_buffer: buf.freeze(),
             ..Default::default()
         };
-        self.uni_packet("RegPrxySvc.infoSync", pkt.freeze())
+        self.uni_packet(commands::REG_PRXY_SVC_INFO_SYNC, pkt.freeze())
     }
 }
@@ -1,8 +1,10 @@
 use crate::command::common::PbToBytes;
+use crate::commands;
 use crate::pb::sig_act;
 use crate::protocol::packet::Packet;
 
 impl super::super::super::Engine {
+    #[must_use]
     pub fn build_update_signature_packet(&self, signature: String) -> Packet {
         let req = sig_act::ReqBody {
             cmd: Some(2),
@@ -30,6 +32,6 @@ impl super::super::super::Engine {
             source: Some(1),
             ..Default::default()
         };
-        self.uni_packet("Signature.auth", req.to_bytes())
+        self.uni_packet(commands::SIGNATURE_AUTH, req.to_bytes())
     }
 }
@@ -5,12 +5,14 @@ use jcers::JcePut;
 
 use crate::command::common::pack_uni_request_data;
 use crate::command::common::PbToBytes;
+use crate::commands;
 use crate::jce;
 use crate::protocol::packet::*;
 use crate::structs::CustomOnlineStatus;
 
 impl super::super::super::Engine {
     // StatSvc.SetStatusFromClient
+    #[must_use]
     pub fn build_set_online_status_packet(
         &self,
         online_status: i32,
@@ -53,10 +55,11 @@ impl super::super::super::Engine {
             ..Default::default()
         };
         let pkt = self.svc_req_register_pkt(svc);
-        self.uni_packet("StatSvc.SetStatusFromClient", pkt.freeze())
+        self.uni_packet(commands::STAT_SVC_SET_STATUS_FROM_CLIENT, pkt.freeze())
     }
 
     // StatSvc.register
+    #[must_use]
     pub fn build_client_register_packet(&self) -> Packet {
         let seq = self.next_seq();
         let transport = &self.transport;
@@ -97,7 +100,7 @@ impl super::super::super::Engine {
             encrypt_type: EncryptType::D2Key,
             seq_id: seq as i32,
             body: pkt.freeze(),
-            command_name: "StatSvc.register".into(),
+            command_name: commands::STAT_SVC_REGISTER.into(),
             uin: self.uin(),
             ..Default::default()
         }
@@ -123,6 +126,7 @@ impl super::super::super::Engine {
     }
 
     // StatSvc.GetDevLoginInfo
+    #[must_use]
     pub fn build_device_list_request_packet(&self) -> Packet {
         let transport = &self.transport;
         let req = jce::SvcReqGetDevLoginInfo {
@@ -146,10 +150,11 @@ impl super::super::super::Engine {
             s_buffer: buf.freeze(),
             ..Default::default()
         };
-        self.uni_packet("StatSvc.GetDevLoginInfo", pkt.freeze())
+        self.uni_packet(commands::STAT_SVC_GET_DEV_LOGIN_INFO, pkt.freeze())
     }
 
     // StatSvc.RspMSFForceOffline
+    #[must_use]
     pub fn build_msf_force_offline_rsp(&self, uin: i64, seq_no: i64) -> Packet {
         let rsp = jce::RspMSFForceOffline {
             uin,
@@ -169,6 +174,6 @@ impl super::super::super::Engine {
             s_buffer: buf.freeze(),
             ..Default::default()
         };
-        self.uni_packet("StatSvc.RspMSFForceOffline", pkt.freeze())
+        self.uni_packet(commands::STAT_SVC_RSP_MSF_FORCE_OFFLINE, pkt.freeze())
     }
 }
@@ -4,11 +4,13 @@ use bytes::{BufMut, Bytes, BytesMut};
 use jcers::JcePut;
 
 use crate::command::common::{pack_uni_request_data, PbToBytes};
+use crate::commands;
 use crate::protocol::packet::*;
 use crate::{jce, pb};
 
 impl super::super::super::Engine {
     // SummaryCard.ReqSummaryCard
+    #[must_use]
     pub fn build_summary_card_request_packet(&self, target: i64) -> Packet {
         let seq = self.next_seq();
         let gate = pb::profilecard::GateVaProfileGateReq {
@@ -98,6 +100,6 @@ impl super::super::super::Engine {
             s_buffer: buf.freeze(),
             ..Default::default()
         };
-        self.uni_packet("SummaryCard.ReqSummaryCard", pkt.freeze())
+        self.uni_packet(commands::SUMMARY_CARD_REQ_SUMMARY_CARD, pkt.freeze())
     }
 }
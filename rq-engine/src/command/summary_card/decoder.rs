@@ -1,16 +1,38 @@
 use bytes::{Buf, Bytes};
 use jcers::Jce;
 
-use crate::structs::SummaryCardInfo;
+use crate::structs::{Badge, SummaryCardInfo};
 use crate::{jce, RQError, RQResult};
 
 impl super::super::super::Engine {
     // SummaryCard.ReqSummaryCard
-    pub fn decode_summary_card_response(&self, mut payload: Bytes) -> RQResult<SummaryCardInfo> {
+    pub fn decode_summary_card_response(
+        &self,
+        uin: i64,
+        mut payload: Bytes,
+    ) -> RQResult<SummaryCardInfo> {
         let mut request: jce::RequestPacket =
             jcers::from_buf(&mut payload).map_err(RQError::from)?;
         let mut data: jce::RequestDataVersion2 =
             jcers::from_buf(&mut request.s_buffer).map_err(RQError::from)?;
+        // 用户不存在时 RespHead 返回非 0 且没有 RespSummaryCard
+        let result = data
+            .map
+            .remove("RespHead")
+            .and_then(|mut m| m.remove("SummaryCard.RespHead"))
+            .map(|mut head| {
+                head.advance(1);
+                let result = Jce::new(&mut head).get_by_tag::<i32>(1);
+                result.unwrap_or_default()
+            })
+            .unwrap_or_default();
+        if result != 0 {
+            return Err(if data.map.contains_key("RespSummaryCard") {
+                RQError::UnsuccessfulRetCode(result)
+            } else {
+                RQError::UserNotFound(uin)
+            });
+        }
         let mut rsp = {
             let mut tmp = data
                 .map
@@ -26,9 +48,11 @@ impl super::super::super::Engine {
             }
         };
         rsp.advance(1);
+        // 可选字段各自用新的 reader 读取，缺失时不影响必需字段的读取位置
+        let optional = rsp.clone();
         let mut rsp = Jce::new(&mut rsp);
 
-        let info = SummaryCardInfo {
+        let mut info = SummaryCardInfo {
             sex: rsp.get_by_tag(1).map_err(RQError::Jce)?,
             age: rsp.get_by_tag(2).map_err(RQError::Jce)?,
             nickname: rsp.get_by_tag(3).map_err(RQError::Jce)?,
@@ -40,7 +64,92 @@ impl super::super::super::Engine {
             login_days: rsp.get_by_tag(36).map_err(RQError::Jce)?,
             ..Default::default()
         };
-        // TODO more info
+        // RespSummaryCard: 6 strProvince, 19 strCountry, 33 uSignModifyTime,
+        // 44 strSchool, 45 strHometown, 47 strProfession, 48 strCompany,
+        // 49 vPersonalTags, 50 vMedalInfo
+        info.province = Jce::new(&mut optional.clone())
+            .get_by_tag(6)
+            .unwrap_or_default();
+        info.country = Jce::new(&mut optional.clone())
+            .get_by_tag(19)
+            .unwrap_or_default();
+        info.sign_date = Jce::new(&mut optional.clone())
+            .get_by_tag::<i64>(33)
+            .ok()
+            .filter(|t| *t > 0);
+        info.college = Jce::new(&mut optional.clone())
+            .get_by_tag(44)
+            .unwrap_or_default();
+        info.hometown = Jce::new(&mut optional.clone())
+            .get_by_tag(45)
+            .unwrap_or_default();
+        info.profession = Jce::new(&mut optional.clone())
+            .get_by_tag(47)
+            .unwrap_or_default();
+        info.company = Jce::new(&mut optional.clone())
+            .get_by_tag(48)
+            .unwrap_or_default();
+        info.personal_tags = Jce::new(&mut optional.clone())
+            .get_by_tag(49)
+            .unwrap_or_default();
+        info.badges = Jce::new(&mut optional.clone())
+            .get_by_tag::<Vec<jce::SummaryCardBadge>>(50)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|b| Badge {
+                id: b.id,
+                name: b.name,
+                level: b.level,
+            })
+            .collect();
         Ok(info)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use jcers::JcePut;
+
+    use super::*;
+    use crate::command::common::pack_uni_request_data;
+    use crate::protocol::device::Device;
+    use crate::protocol::version::ANDROID_WATCH;
+    use crate::Engine;
+
+    #[derive(Default, JcePut)]
+    struct RespHead {
+        #[jce(0)]
+        version: i32,
+        #[jce(1)]
+        result: i32,
+    }
+
+    #[test]
+    fn test_decode_summary_card_user_not_found() {
+        let head = RespHead {
+            version: 2,
+            result: 201,
+        };
+        let data = jce::RequestDataVersion2 {
+            map: HashMap::from([(
+                "RespHead".to_string(),
+                HashMap::from([(
+                    "SummaryCard.RespHead".to_string(),
+                    pack_uni_request_data(&head.freeze()),
+                )]),
+            )]),
+        };
+        let pkt = jce::RequestPacket {
+            i_version: 2,
+            s_buffer: data.freeze(),
+            ..Default::default()
+        };
+        let engine = Engine::new(Device::random(), ANDROID_WATCH);
+        assert!(matches!(
+            engine.decode_summary_card_response(12345, pkt.freeze()),
+            Err(RQError::UserNotFound(12345))
+        ));
+    }
+}
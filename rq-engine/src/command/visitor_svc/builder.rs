@@ -1,4 +1,5 @@
 use crate::command::common::pack_uni_request_data;
+use crate::commands;
 use crate::hex::decode_hex;
 use crate::jce;
 use crate::protocol::packet::Packet;
@@ -8,6 +9,7 @@ use std::collections::HashMap;
 
 impl super::super::super::Engine {
     // VisitorSvc.ReqFavorite
+    #[must_use]
     pub fn build_send_like_packet(&self, uin: i64, count: i32) -> Packet {
         let seq = self.next_seq();
         let req = jce::ReqFavorite {
@@ -37,6 +39,6 @@ impl super::super::super::Engine {
             s_buffer: buf.freeze(),
             ..Default::default()
         };
-        self.uni_packet("VisitorSvc.ReqFavorite", pkt.freeze())
+        self.uni_packet(commands::VISITOR_SVC_REQ_FAVORITE, pkt.freeze())
     }
 }
@@ -4,6 +4,7 @@ use chrono::Utc;
 use crate::binary::BinaryWriter;
 use crate::command::wtlogin::builder::utils::*;
 use crate::command::wtlogin::tlv_writer::*;
+use crate::commands;
 use crate::protocol::{
     oicq::{self, EncryptionMethod},
     packet::{EncryptType, Packet, PacketType},
@@ -12,6 +13,7 @@ use crate::protocol::{
 
 impl super::super::super::Engine {
     // wtlogin.trans_emp
+    #[must_use]
     pub fn build_qrcode_fetch_request_packet(&self) -> Packet {
         let watch = get_version(Protocol::AndroidWatch);
         let transport = &self.transport;
@@ -59,12 +61,13 @@ impl super::super::super::Engine {
             encrypt_type: EncryptType::EmptyKey,
             seq_id: seq as i32,
             body: req,
-            command_name: "wtlogin.trans_emp".into(),
+            command_name: commands::WTLOGIN_TRANS_EMP.into(),
             ..Default::default()
         }
     }
 
     // wtlogin.trans_emp
+    #[must_use]
     pub fn build_qrcode_result_query_request_packet(&self, sig: &[u8]) -> Packet {
         let seq = self.next_seq();
         let req = self.build_oicq_request_packet(0, 0x812, &{
@@ -92,12 +95,13 @@ impl super::super::super::Engine {
             encrypt_type: EncryptType::EmptyKey,
             seq_id: seq as i32,
             body: req,
-            command_name: "wtlogin.trans_emp".into(),
+            command_name: commands::WTLOGIN_TRANS_EMP.into(),
             ..Default::default()
         }
     }
 
     // wtlogin.login
+    #[must_use]
     pub fn build_qrcode_login_packet(&self, t106: &[u8], t16a: &[u8], t318: &[u8]) -> Packet {
         let seq = self.next_seq();
         let transport = &self.transport;
@@ -206,13 +210,14 @@ impl super::super::super::Engine {
             encrypt_type: EncryptType::EmptyKey,
             seq_id: seq as i32,
             body: req,
-            command_name: "wtlogin.login".to_string(),
+            command_name: commands::WTLOGIN_LOGIN.into(),
             uin: self.uin(),
             message: "".to_string(),
         }
     }
 
     // wtlogin.login
+    #[must_use]
     pub fn build_device_lock_login_packet(&self) -> Packet {
         let seq = self.next_seq();
         let transport = &self.transport;
@@ -235,13 +240,14 @@ impl super::super::super::Engine {
             encrypt_type: EncryptType::EmptyKey,
             seq_id: seq as i32,
             body: req,
-            command_name: "wtlogin.login".into(),
+            command_name: commands::WTLOGIN_LOGIN.into(),
             uin: self.uin(),
             ..Default::default()
         }
     }
 
     // wtlogin.login
+    #[must_use]
     pub fn build_captcha_packet(&self, result: String, sign: &[u8]) -> Packet {
         let seq = self.next_seq();
         let transport = &self.transport;
@@ -265,13 +271,14 @@ impl super::super::super::Engine {
             encrypt_type: EncryptType::EmptyKey,
             seq_id: seq as i32,
             body: req,
-            command_name: "wtlogin.login".into(),
+            command_name: commands::WTLOGIN_LOGIN.into(),
             uin: self.uin(),
             ..Default::default()
         }
     }
 
     // wtlogin.login
+    #[must_use]
     pub fn build_sms_request_packet(&self) -> Packet {
         let seq = self.next_seq();
         let transport = &self.transport;
@@ -296,13 +303,14 @@ impl super::super::super::Engine {
             encrypt_type: EncryptType::EmptyKey,
             seq_id: seq as i32,
             body: req,
-            command_name: "wtlogin.login".into(),
+            command_name: commands::WTLOGIN_LOGIN.into(),
             uin: self.uin(),
             ..Default::default()
         }
     }
 
     // wtlogin.login
+    #[must_use]
     pub fn build_sms_code_submit_packet(&self, code: &str) -> Packet {
         let seq = self.next_seq();
         let transport = &self.transport;
@@ -328,13 +336,14 @@ impl super::super::super::Engine {
             encrypt_type: EncryptType::EmptyKey,
             seq_id: seq as i32,
             body: req,
-            command_name: "wtlogin.login".into(),
+            command_name: commands::WTLOGIN_LOGIN.into(),
             uin: self.uin(),
             ..Default::default()
         }
     }
 
     // wtlogin.login
+    #[must_use]
     pub fn build_ticket_submit_packet(&self, ticket: &str) -> Packet {
         let seq = self.next_seq();
         let transport = &self.transport;
@@ -357,13 +366,14 @@ impl super::super::super::Engine {
             encrypt_type: EncryptType::EmptyKey,
             seq_id: seq as i32,
             body: req,
-            command_name: "wtlogin.login".into(),
+            command_name: commands::WTLOGIN_LOGIN.into(),
             uin: self.uin(),
             ..Default::default()
         }
     }
 
     // wtlogin.exchange_emp
+    #[must_use]
     pub fn build_request_tgtgt_no_pic_sig_packet(&self) -> Packet {
         let seq = self.next_seq();
         let transport = &self.transport;
@@ -469,13 +479,14 @@ impl super::super::super::Engine {
             encrypt_type: EncryptType::EmptyKey,
             seq_id: seq as i32,
             body: codec.encode(m),
-            command_name: "wtlogin.exchange_emp".into(),
+            command_name: commands::WTLOGIN_EXCHANGE_EMP.into(),
             uin: self.uin(),
             message: "".to_string(),
         }
     }
 
     // wtlogin.exchange_emp
+    #[must_use]
     pub fn build_request_change_sig_packet(&self, main_sig_map: Option<u32>) -> Packet {
         let seq = self.next_seq();
         let transport = &self.transport;
@@ -554,13 +565,14 @@ impl super::super::super::Engine {
             encrypt_type: EncryptType::EmptyKey,
             seq_id: seq as i32,
             body: req,
-            command_name: "wtlogin.exchange_emp".into(),
+            command_name: commands::WTLOGIN_EXCHANGE_EMP.into(),
             uin: self.uin(),
             ..Default::default()
         }
     }
 
     // wtlogin.login
+    #[must_use]
     pub fn build_login_packet(&self, password_md5: &[u8], allow_slider: bool) -> Packet {
         let seq = self.next_seq();
         let transport = &self.transport;
@@ -663,7 +675,7 @@ impl super::super::super::Engine {
             encrypt_type: EncryptType::EmptyKey,
             seq_id: seq as i32,
             body: req,
-            command_name: "wtlogin.login".into(),
+            command_name: commands::WTLOGIN_LOGIN.into(),
             uin: self.uin(),
             ..Default::default()
         }
@@ -695,7 +695,7 @@ pub fn t511(domains: Vec<&str>) -> Bytes {
             };
             if index_of != 0 || index_of2 <= 0 {
                 w.put_u8(0x01);
-                w.write_bytes_short(d.as_bytes())
+                w.write_bytes_short(d.as_bytes());
             } else {
                 let mut b: u8;
                 let z: bool;
@@ -0,0 +1,94 @@
+// 数据包 command name，可用于 `wait_packet` 等按名称匹配数据包的场景
+
+// 登录
+pub const WTLOGIN_LOGIN: &str = "wtlogin.login";
+pub const WTLOGIN_TRANS_EMP: &str = "wtlogin.trans_emp";
+pub const WTLOGIN_EXCHANGE_EMP: &str = "wtlogin.exchange_emp";
+pub const HEARTBEAT_ALIVE: &str = "Heartbeat.Alive";
+
+// StatSvc
+pub const STAT_SVC_REGISTER: &str = "StatSvc.register";
+pub const STAT_SVC_SET_STATUS_FROM_CLIENT: &str = "StatSvc.SetStatusFromClient";
+pub const STAT_SVC_GET_DEV_LOGIN_INFO: &str = "StatSvc.GetDevLoginInfo";
+pub const STAT_SVC_RSP_MSF_FORCE_OFFLINE: &str = "StatSvc.RspMSFForceOffline";
+pub const STAT_SVC_REQ_MSF_OFFLINE: &str = "StatSvc.ReqMSFOffline";
+
+// 消息
+pub const MESSAGE_SVC_PB_SEND_MSG: &str = "MessageSvc.PbSendMsg";
+pub const MESSAGE_SVC_PB_GET_MSG: &str = "MessageSvc.PbGetMsg";
+pub const MESSAGE_SVC_PB_DELETE_MSG: &str = "MessageSvc.PbDeleteMsg";
+pub const MESSAGE_SVC_PB_GET_GROUP_MSG: &str = "MessageSvc.PbGetGroupMsg";
+pub const MESSAGE_SVC_PUSH_NOTIFY: &str = "MessageSvc.PushNotify";
+pub const MESSAGE_SVC_PUSH_FORCE_OFFLINE: &str = "MessageSvc.PushForceOffline";
+pub const MESSAGE_SVC_PUSH_READED: &str = "MessageSvc.PushReaded";
+pub const PB_MESSAGE_SVC_PB_MSG_WITH_DRAW: &str = "PbMessageSvc.PbMsgWithDraw";
+pub const PB_MESSAGE_SVC_PB_MSG_READED_REPORT: &str = "PbMessageSvc.PbMsgReadedReport";
+
+// 推送
+pub const ONLINE_PUSH_PB_PUSH_GROUP_MSG: &str = "OnlinePush.PbPushGroupMsg";
+pub const ONLINE_PUSH_PB_PUSH_TRANS_MSG: &str = "OnlinePush.PbPushTransMsg";
+pub const ONLINE_PUSH_PB_C2C_MSG_SYNC: &str = "OnlinePush.PbC2CMsgSync";
+pub const ONLINE_PUSH_REQ_PUSH: &str = "OnlinePush.ReqPush";
+pub const ONLINE_PUSH_RESP_PUSH: &str = "OnlinePush.RespPush";
+pub const ONLINE_PUSH_SID_TICKET_EXPIRED: &str = "OnlinePush.SidTicketExpired";
+pub const CONFIG_PUSH_SVC_PUSH_REQ: &str = "ConfigPushSvc.PushReq";
+pub const CONFIG_PUSH_SVC_PUSH_RESP: &str = "ConfigPushSvc.PushResp";
+pub const REG_PRXY_SVC_PUSH_PARAM: &str = "RegPrxySvc.PushParam";
+pub const REG_PRXY_SVC_GET_OFF_MSG: &str = "RegPrxySvc.getOffMsg";
+pub const REG_PRXY_SVC_INFO_SYNC: &str = "RegPrxySvc.infoSync";
+pub const REG_PRXY_SVC_GET_MSG_V2: &str = "RegPrxySvc.GetMsgV2";
+pub const REG_PRXY_SVC_PB_GET_MSG: &str = "RegPrxySvc.PbGetMsg";
+pub const REG_PRXY_SVC_NOTICE_END: &str = "RegPrxySvc.NoticeEnd";
+
+// 好友、群列表
+pub const FRIENDLIST_GET_FRIEND_GROUP_LIST: &str = "friendlist.getFriendGroupList";
+pub const FRIENDLIST_GET_TROOP_LIST_REQ_V2: &str = "friendlist.GetTroopListReqV2";
+pub const FRIENDLIST_GET_TROOP_MEMBER_LIST_REQ: &str = "friendlist.GetTroopMemberListReq";
+pub const FRIENDLIST_MODIFY_GROUP_CARD_REQ: &str = "friendlist.ModifyGroupCardReq";
+pub const FRIENDLIST_DEL_FRIEND: &str = "friendlist.delFriend";
+pub const FRIENDLIST_SET_GROUP_REQ: &str = "friendlist.SetGroupReq";
+pub const FRIENDLIST_MOV_GROUP_MEM_REQ: &str = "friendlist.MovGroupMemReq";
+pub const GROUP_MEMBER_CARD_GET_GROUP_MEMBER_CARD_INFO: &str =
+    "group_member_card.get_group_member_card_info";
+pub const GROUP_ANONYMOUS_GENERATE_NICK_GROUP: &str = "group_anonymous_generate_nick.group";
+pub const SUMMARY_CARD_REQ_SUMMARY_CARD: &str = "SummaryCard.ReqSummaryCard";
+pub const VISITOR_SVC_REQ_FAVORITE: &str = "VisitorSvc.ReqFavorite";
+pub const SIGNATURE_AUTH: &str = "Signature.auth";
+
+// 系统消息
+pub const PROFILE_SERVICE_REQ_SYSTEM_MSG_NEW_GROUP: &str =
+    "ProfileService.Pb.ReqSystemMsgNew.Group";
+pub const PROFILE_SERVICE_REQ_SYSTEM_MSG_NEW_FRIEND: &str =
+    "ProfileService.Pb.ReqSystemMsgNew.Friend";
+pub const PROFILE_SERVICE_REQ_SYSTEM_MSG_ACTION_GROUP: &str =
+    "ProfileService.Pb.ReqSystemMsgAction.Group";
+pub const PROFILE_SERVICE_REQ_SYSTEM_MSG_ACTION_FRIEND: &str =
+    "ProfileService.Pb.ReqSystemMsgAction.Friend";
+pub const PROFILE_SERVICE_GROUP_MNG_REQ: &str = "ProfileService.GroupMngReq";
+
+// 图片、语音
+pub const IMG_STORE_GROUP_PIC_UP: &str = "ImgStore.GroupPicUp";
+pub const LONG_CONN_OFF_PIC_UP: &str = "LongConn.OffPicUp";
+pub const PTT_STORE_GROUP_PTT_DOWN: &str = "PttStore.GroupPttDown";
+pub const PTT_CENTER_SVR_APPLY_DOWNLOAD: &str =
+    "PttCenterSvr.pb_pttCenter_CMD_REQ_APPLY_DOWNLOAD-1200";
+pub const MULTI_MSG_APPLY_UP: &str = "MultiMsg.ApplyUp";
+
+// oidb
+pub const OIDB_SVC_0X4FF_9_IMCORE: &str = "OidbSvc.0x4ff_9_IMCore";
+pub const OIDB_SVC_0X55C_1: &str = "OidbSvc.0x55c_1";
+pub const OIDB_SVC_0X570_8: &str = "OidbSvc.0x570_8";
+pub const OIDB_SVC_0X758: &str = "OidbSvc.0x758";
+pub const OIDB_SVC_0X88D_0: &str = "OidbSvc.0x88d_0";
+pub const OIDB_SVC_0X89A_0: &str = "OidbSvc.0x89a_0";
+pub const OIDB_SVC_0X8A0_0: &str = "OidbSvc.0x8a0_0";
+pub const OIDB_SVC_0X6D6_2: &str = "OidbSvc.0x6d6_2";
+pub const OIDB_SVC_0X6D8_1: &str = "OidbSvc.0x6d8_1";
+pub const OIDB_SVC_0X8A7_0: &str = "OidbSvc.0x8a7_0";
+pub const OIDB_SVC_0X8BA_0: &str = "OidbSvc.0x8ba_0";
+pub const OIDB_SVC_0X8FC_2: &str = "OidbSvc.0x8fc_2";
+pub const OIDB_SVC_0X990: &str = "OidbSvc.0x990";
+pub const OIDB_SVC_0XB77_9: &str = "OidbSvc.0xb77_9";
+pub const OIDB_SVC_0XE07_0: &str = "OidbSvc.0xe07_0";
+pub const OIDB_SVC_0XEAC: &str = "OidbSvc.0xeac";
+pub const OIDB_SVC_0XED3: &str = "OidbSvc.0xed3";
@@ -12,7 +12,7 @@ pub trait IEncryptMethod {
     fn do_encrypt(&self, data: &[u8], key: &[u8]) -> Vec<u8>;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct EncryptECDH {
     pub initial_share_key: Bytes,
     pub public_key: Bytes,
@@ -47,6 +47,31 @@ pub fn qqtea_encrypt(text: &[u8], key: &[u8]) -> Vec<u8> {
 }
 
 pub fn qqtea_decrypt(text: &[u8], key: &[u8]) -> Vec<u8> {
+    let result = decrypt_blocks(text, key);
+
+    let begin_pos = ((result[0] as usize) & 7) + 3;
+    let end_pos = result.len() - 7;
+
+    result[begin_pos..end_pos].to_owned()
+}
+
+// 解密并校验密文长度及填充，数据损坏或 key 不正确时返回 None
+pub fn qqtea_decrypt_checked(text: &[u8], key: &[u8]) -> Option<Vec<u8>> {
+    if key.len() != 16 || text.len() < 16 || text.len() % 8 != 0 {
+        return None;
+    }
+    let result = decrypt_blocks(text, key);
+
+    let begin_pos = ((result[0] as usize) & 7) + 3;
+    let end_pos = result.len() - 7;
+
+    if begin_pos > end_pos || result[end_pos..].iter().any(|b| *b != 0) {
+        return None;
+    }
+    Some(result[begin_pos..end_pos].to_owned())
+}
+
+fn decrypt_blocks(text: &[u8], key: &[u8]) -> Vec<u8> {
     let mut work_block: Vec<u64> = vec![0; text.len() / 8];
 
     BigEndian::read_u64_into(text, &mut work_block);
@@ -76,10 +101,25 @@ pub fn qqtea_decrypt(text: &[u8], key: &[u8]) -> Vec<u8> {
 
     BigEndian::write_u64_into(&work_block, &mut result);
 
-    let begin_pos = ((result[0] as usize) & 7) + 3;
-    let end_pos = result.len() - 7;
+    result
+}
 
-    result[begin_pos..end_pos].to_owned()
+#[cfg(test)]
+mod tests {
+    use super::{qqtea_decrypt, qqtea_decrypt_checked, qqtea_encrypt};
+
+    #[test]
+    fn test_qqtea_decrypt_checked() {
+        let key = [1u8; 16];
+        let mut data = qqtea_encrypt(b"hello world", &key);
+        assert_eq!(qqtea_decrypt_checked(&data, &key).unwrap(), b"hello world");
+        assert_eq!(qqtea_decrypt(&data, &key), b"hello world");
+        assert!(qqtea_decrypt_checked(&data, &[2u8; 16]).is_none());
+        let last = data.len() - 1;
+        data[last] ^= 0xFF;
+        assert!(qqtea_decrypt_checked(&data, &key).is_none());
+        assert!(qqtea_decrypt_checked(&data[..8], &key).is_none());
+    }
 }
 
 mod tea {
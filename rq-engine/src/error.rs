@@ -1,7 +1,10 @@
 use std::io;
+use std::time::Duration;
 
 use thiserror::Error;
 
+use crate::protocol::version::Protocol;
+
 pub type RQResult<T> = Result<T, RQError>;
 
 #[derive(Error, Debug)]
@@ -18,12 +21,18 @@ pub enum RQError {
     #[error("command_name mismatch, expected {0} get {1}")]
     CommandNameMismatch(String, String),
 
-    #[error("timeout error")]
-    Timeout,
+    #[error("timed out after {elapsed:?} waiting for response to command '{command}'")]
+    Timeout { command: String, elapsed: Duration },
 
     #[error("network error")]
     Network,
 
+    #[error("out packet queue is full")]
+    QueueFull,
+
+    #[error("protobuf decode error, {0}")]
+    Prost(#[from] prost::DecodeError),
+
     #[error("jce error, {0}")]
     Jce(#[from] jcers::JceError),
     #[error("io error, {0}")]
@@ -43,9 +52,37 @@ pub enum RQError {
     PacketDropped,
     #[error("session expired")]
     SessionExpired,
-    #[error("session expired, {0}")]
+    #[error("unsuccessful ret code {0}")]
     UnsuccessfulRetCode(i32),
+    #[error("rate limited by server, {0}")]
+    RateLimited(String),
 
     #[error("Token login failed")]
     TokenLoginFailed,
+
+    #[error("unsupported on protocol {0:?}")]
+    UnsupportedOnProtocol(Protocol),
+
+    #[error("permission denied")]
+    PermissionDenied,
+
+    #[error("user {0} not found")]
+    UserNotFound(i64),
+
+    #[error("invalid argument, {0}")]
+    InvalidArgument(String),
+
+    #[error("client is shutting down")]
+    ShuttingDown,
+}
+
+impl From<RQError> for io::Error {
+    fn from(err: RQError) -> Self {
+        match err {
+            RQError::IO(err) => err,
+            RQError::Timeout { .. } => io::Error::new(io::ErrorKind::TimedOut, err),
+            RQError::Network => io::Error::new(io::ErrorKind::ConnectionAborted, err),
+            err => io::Error::new(io::ErrorKind::Other, err),
+        }
+    }
 }
@@ -4,7 +4,7 @@ use std::sync::atomic::{AtomicI32, Ordering};
 use bytes::Bytes;
 
 use crate::command::common::PbToBytes;
-use crate::{pb, RQError, RQResult};
+use crate::{pb, RQResult};
 
 #[derive(Default)]
 pub struct Session {
@@ -97,8 +97,7 @@ impl Session {
     }
 
     pub fn decode_rsp_head(&self, payload: Bytes) -> RQResult<pb::RspDataHighwayHead> {
-        pb::RspDataHighwayHead::from_bytes(&payload)
-            .map_err(|_| RQError::Other("RspDataHighwayHead".into()))
+        Ok(pb::RspDataHighwayHead::from_bytes(&payload)?)
     }
 
     pub fn build_heartbreak(&self) -> Bytes {
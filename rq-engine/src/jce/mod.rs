@@ -832,6 +832,47 @@ JceStruct!(SummaryCardReqSearch {
     3 => req_services:  Vec<Bytes>, // todo // busi
 });
 
+JceStruct!(FriendGroupInfo {
+    0 => group_id: u8,
+    1 => group_name: String,
+    2 => friend_count: i32,
+    3 => online_friend_count: i32,
+    4 => seq_id: u8,
+    5 => sqq_online_count: i32,
+});
+
+JceStruct!(SetGroupReq {
+    0 => reqtype: i32,
+    1 => uin: i64,
+    2 => vec_body: Bytes,
+});
+
+JceStruct!(MovGroupMemReq {
+    0 => uin: i64,
+    1 => reqtype: i16,
+    2 => vec_body: Bytes,
+});
+
+JceStruct!(SummaryCardBadge {
+    0 => id: i32,
+    1 => name: String,
+    2 => level: i32,
+});
+
+JceStruct!(SetGroupResp {
+    0 => reqtype: i32,
+    1 => result: u8,
+    2 => uin: i64,
+    3 => error_code: i16,
+});
+
+JceStruct!(MovGroupMemResp {
+    0 => uin: i64,
+    1 => result: u8,
+    2 => reqtype: i16,
+    3 => error_code: i16,
+});
+
 JceStruct!(DelFriendReq {
     0 => uin: i64,
     1 => del_uin: i64,
@@ -1,6 +1,7 @@
 #![feature(type_alias_impl_trait)]
 
 use std::sync::atomic::{AtomicI32, AtomicI64, AtomicU16, Ordering};
+use std::sync::Arc;
 
 use bytes::Bytes;
 use rand::Rng;
@@ -15,6 +16,7 @@ pub use crate::token::Token;
 
 pub mod binary;
 pub mod command;
+pub mod commands;
 pub mod common;
 pub mod crypto;
 pub mod error;
@@ -31,30 +33,35 @@ pub mod wtlogin;
 // build_packet: param -> bytes
 // decode_packet: bytes -> struct
 // this should be wrapped in a rwlock (readonly after login)
+// 所有 build 方法只需要 &self，seq 都是原子量，并发构建不需要写锁
+// 外层的 RwLock 只保护登录和刷新 token 时写入的 transport.sig，登录后只有读锁，读锁之间不会互相等待，
+// 所以调用处保留 engine.read().await，不再为去掉这层锁把 transport 也改成内部可变
 // TODO: build library for other language
 // no async and await
+// clone 出的 Engine 与原 Engine 共享 uin 和各个 seq，可以在不持有锁的情况下构建数据包
+#[derive(Clone)]
 pub struct Engine {
-    pub uin: AtomicI64,
+    pub uin: Arc<AtomicI64>,
     pub transport: Transport,
-    pub seq_id: AtomicU16,
-    pub request_packet_request_id: AtomicI32,
-    pub group_seq: AtomicI32,
-    pub friend_seq: AtomicI32,
-    pub group_data_trans_seq: AtomicI32,
-    pub highway_apply_up_seq: AtomicI32,
+    pub seq_id: Arc<AtomicU16>,
+    pub request_packet_request_id: Arc<AtomicI32>,
+    pub group_seq: Arc<AtomicI32>,
+    pub friend_seq: Arc<AtomicI32>,
+    pub group_data_trans_seq: Arc<AtomicI32>,
+    pub highway_apply_up_seq: Arc<AtomicI32>,
 }
 
 impl Engine {
     pub fn new(device: Device, version: &'static Version) -> Self {
         Self {
-            uin: AtomicI64::new(0),
+            uin: Arc::new(AtomicI64::new(0)),
             transport: Transport::new(device, version),
-            seq_id: AtomicU16::new(0x3635),
-            request_packet_request_id: AtomicI32::new(1921334513),
-            group_seq: AtomicI32::new(rand::thread_rng().gen_range(0..20000)),
-            friend_seq: AtomicI32::new(rand::thread_rng().gen_range(0..20000)),
-            group_data_trans_seq: AtomicI32::new(rand::thread_rng().gen_range(0..20000)),
-            highway_apply_up_seq: AtomicI32::new(rand::thread_rng().gen_range(0..20000)),
+            seq_id: Arc::new(AtomicU16::new(0x3635)),
+            request_packet_request_id: Arc::new(AtomicI32::new(1921334513)),
+            group_seq: Arc::new(AtomicI32::new(rand::thread_rng().gen_range(0..20000))),
+            friend_seq: Arc::new(AtomicI32::new(rand::thread_rng().gen_range(0..20000))),
+            group_data_trans_seq: Arc::new(AtomicI32::new(rand::thread_rng().gen_range(0..20000))),
+            highway_apply_up_seq: Arc::new(AtomicI32::new(rand::thread_rng().gen_range(0..20000))),
         }
     }
 
@@ -62,8 +69,14 @@ impl Engine {
         self.uin.load(Ordering::Relaxed)
     }
 
+    // seq 为 0 可能被服务端认为没有 seq，溢出后跳过 0
     pub fn next_seq(&self) -> u16 {
-        self.seq_id.fetch_add(1, Ordering::Relaxed)
+        let seq = self.seq_id.fetch_add(1, Ordering::Relaxed);
+        if seq == 0 {
+            self.seq_id.fetch_add(1, Ordering::Relaxed)
+        } else {
+            seq
+        }
     }
 
     pub fn next_packet_seq(&self) -> i32 {
@@ -72,19 +85,19 @@ impl Engine {
     }
 
     pub fn next_group_seq(&self) -> i32 {
-        self.group_seq.fetch_add(2, Ordering::Relaxed)
+        next_seq_i32(&self.group_seq)
     }
 
     pub fn next_friend_seq(&self) -> i32 {
-        self.friend_seq.fetch_add(2, Ordering::Relaxed)
+        next_seq_i32(&self.friend_seq)
     }
 
     pub fn next_group_data_trans_seq(&self) -> i32 {
-        self.group_data_trans_seq.fetch_add(2, Ordering::Relaxed)
+        next_seq_i32(&self.group_data_trans_seq)
     }
 
     pub fn next_highway_apply_seq(&self) -> i32 {
-        self.highway_apply_up_seq.fetch_add(2, Ordering::Relaxed)
+        next_seq_i32(&self.highway_apply_up_seq)
     }
 
     pub fn gen_token(&self) -> Token {
@@ -115,3 +128,61 @@ impl Engine {
         self.transport.oicq_codec.wt_session_ticket_key = Bytes::from(token.wt_session_ticket_key);
     }
 }
+
+// 每次 +2，溢出时重新随机，避免出现负数 seq
+fn next_seq_i32(seq: &AtomicI32) -> i32 {
+    seq.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |v| {
+        Some(
+            v.checked_add(2)
+                .unwrap_or_else(|| rand::random::<u16>() as i32),
+        )
+    })
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::atomic::AtomicI32;
+
+    use crate::protocol::device::Device;
+    use crate::protocol::version::{get_version, Protocol};
+    use crate::{next_seq_i32, Engine};
+
+    #[test]
+    fn test_next_seq_skip_zero() {
+        let engine = Engine::new(Device::random(), get_version(Protocol::IPad));
+        for _ in 0..=u16::MAX as usize + 1 {
+            assert_ne!(engine.next_seq(), 0);
+        }
+    }
+
+    #[test]
+    fn test_next_seq_i32_no_negative() {
+        let seq = AtomicI32::new(i32::MAX - 3);
+        for _ in 0..4 {
+            assert!(next_seq_i32(&seq) >= 0);
+        }
+    }
+
+    // clone 出的 Engine 共享 seq，并发构建时不会重复
+    #[test]
+    fn test_cloned_engine_shares_seq() {
+        let engine = Engine::new(Device::random(), get_version(Protocol::IPad));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let engine = engine.clone();
+                std::thread::spawn(move || {
+                    (0..1000)
+                        .map(|_| engine.next_group_seq())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let seqs: HashSet<i32> = handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect();
+        assert_eq!(seqs.len(), 4000);
+    }
+}
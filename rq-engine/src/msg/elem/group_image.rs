@@ -1,12 +1,11 @@
 use std::fmt;
 
-use serde::{Deserialize, Serialize};
-
 use crate::msg::elem::flash_image::FlashImage;
 use crate::pb::msg;
 use crate::pb::msg::CustomFace;
 
-#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[derive(Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GroupImage {
     pub image_id: String,
     pub file_id: i64,
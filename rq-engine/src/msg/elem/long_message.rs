@@ -0,0 +1,69 @@
+use std::io::Write;
+
+use flate2::{write::ZlibEncoder, Compression};
+
+use crate::pb::msg;
+
+/// 长消息卡片，res_id 为 MultiMsg.ApplyUp 返回的 resid
+#[derive(Default, Debug, Clone)]
+pub struct LongMessage {
+    pub res_id: String,
+    pub brief: String,
+}
+
+impl LongMessage {
+    pub fn new(res_id: String, brief: String) -> Self {
+        Self { res_id, brief }
+    }
+
+    fn xml(&self) -> String {
+        // 预览只显示前 30 个字
+        let mut brief: String = self.brief.chars().take(30).collect();
+        if brief.chars().count() < self.brief.chars().count() {
+            brief.push('…');
+        }
+        let brief = escape_xml(&brief);
+        format!(
+            "<?xml version='1.0' encoding='UTF-8' standalone='yes' ?>\
+<msg serviceID=\"35\" templateID=\"1\" action=\"viewMultiMsg\" brief=\"{brief}\" m_resid=\"{res_id}\" \
+m_fileName=\"{file_name}\" sourceMsgId=\"0\" url=\"\" flag=\"3\" adverSign=\"0\" multiMsgFlag=\"1\">\
+<item layout=\"1\"><title>{brief}</title><hr hidden=\"false\" style=\"0\" />\
+<summary>点击查看完整消息</summary></item>\
+<source name=\"聊天记录\" icon=\"\" action=\"\" appid=\"-1\" /></msg>",
+            brief = brief,
+            res_id = escape_xml(&self.res_id),
+            file_name = chrono::Utc::now().timestamp(),
+        )
+    }
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+impl From<LongMessage> for Vec<msg::elem::Elem> {
+    fn from(e: LongMessage) -> Self {
+        vec![
+            msg::elem::Elem::RichMsg(msg::RichMsg {
+                template1: Some({
+                    let mut encoder = ZlibEncoder::new(vec![1], Compression::default());
+                    encoder.write_all(e.xml().as_bytes()).ok();
+                    encoder.finish().unwrap_or_default()
+                }),
+                service_id: Some(35),
+                msg_res_id: Some(vec![]),
+                ..Default::default()
+            }),
+            msg::elem::Elem::GeneralFlags(msg::GeneralFlags {
+                long_text_flag: Some(1),
+                long_text_resid: Some(e.res_id),
+                pb_reserve: Some(vec![0x78, 0x00, 0xF8, 0x01, 0x00, 0xC8, 0x02, 0x00]),
+                ..Default::default()
+            }),
+        ]
+    }
+}
@@ -0,0 +1,95 @@
+use std::fmt;
+
+use crate::msg::elem::LightApp;
+use crate::pb::msg;
+
+const MINI_APP_NAME: &str = "com.tencent.miniapp_01";
+
+// QQ 小程序，以 LightApp 的 json 发送
+#[derive(Default, Debug, Clone)]
+pub struct MiniApp {
+    pub app_id: String,
+    pub title: String,
+    pub desc: String,
+    pub preview_url: String,
+    pub url: String,
+}
+
+impl MiniApp {
+    // 不是小程序时返回 None
+    pub fn from_light_app(light_app: &LightApp) -> Option<Self> {
+        // 不依赖 serde derive，serde 是可选 feature
+        let content: serde_json::Value = serde_json::from_str(&light_app.content).ok()?;
+        if content["app"].as_str() != Some(MINI_APP_NAME) {
+            return None;
+        }
+        let detail = &content["meta"]["detail_1"];
+        let field = |name: &str| detail[name].as_str().unwrap_or_default().to_string();
+        let qqdocurl = field("qqdocurl");
+        Some(Self {
+            app_id: field("appid"),
+            title: field("title"),
+            desc: field("desc"),
+            preview_url: field("preview"),
+            url: if qqdocurl.is_empty() {
+                field("url")
+            } else {
+                qqdocurl
+            },
+        })
+    }
+}
+
+impl From<MiniApp> for LightApp {
+    fn from(e: MiniApp) -> Self {
+        let content = serde_json::json!({
+            "app": MINI_APP_NAME,
+            "desc": "",
+            "view": "view_8C8E89B49BE609866298ADDFF2DBABA4",
+            "ver": "1.0.0.19",
+            "prompt": format!("[QQ小程序]{}", e.title),
+            "meta": {
+                "detail_1": {
+                    "appid": e.app_id,
+                    "title": e.title,
+                    "desc": e.desc,
+                    "preview": e.preview_url,
+                    "qqdocurl": e.url,
+                    "url": e.url,
+                }
+            }
+        });
+        LightApp::new(content.to_string())
+    }
+}
+
+impl From<MiniApp> for Vec<msg::elem::Elem> {
+    fn from(e: MiniApp) -> Self {
+        LightApp::from(e).into()
+    }
+}
+
+impl fmt::Display for MiniApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[QQ小程序]{}", self.title)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let content = r#"{"app":"com.tencent.miniapp_01","desc":"","view":"view_8C8E89B49BE609866298ADDFF2DBABA4","ver":"1.0.0.19","prompt":"[QQ小程序]哔哩哔哩","meta":{"detail_1":{"appid":"1109937557","desc":"【官方MV】Never Gonna Give You Up","icon":"http://miniapp.gtimg.cn/public/appicon/432b76be3a548fc128acaa6c1ec90131_200.jpg","preview":"pubminishare-30161.picsz.qpic.cn/ca6bb6c3-7f3b-4a5e-9d19-c2b4e0b6b5c7","qqdocurl":"https://b23.tv/BV1GJ411x7h7","title":"哔哩哔哩","url":"m.q.qq.com/a/s/a34c2b1c1dfa2ac9ab3a1d2f6d1e0c1a"}}}"#;
+        let mini_app = MiniApp::from_light_app(&LightApp::new(content.into())).unwrap();
+        assert_eq!(mini_app.app_id, "1109937557");
+        assert_eq!(mini_app.title, "哔哩哔哩");
+        assert_eq!(mini_app.url, "https://b23.tv/BV1GJ411x7h7");
+
+        let encoded = LightApp::from(mini_app.clone());
+        let decoded = MiniApp::from_light_app(&encoded).unwrap();
+        assert_eq!(decoded.app_id, mini_app.app_id);
+        assert!(MiniApp::from_light_app(&LightApp::new("{}".into())).is_none());
+    }
+}
@@ -12,7 +12,9 @@ pub use crate::msg::elem::{
     friend_image::FriendImage,
     group_image::GroupImage,
     light_app::LightApp,
+    long_message::LongMessage,
     market_face::{Dice, FingerGuessing, MarketFace},
+    mini_app::MiniApp,
     red_bag::RedBag,
     reply::Reply,
     text::Text,
@@ -26,7 +28,9 @@ mod flash_image;
 mod friend_image;
 mod group_image;
 mod light_app;
+mod long_message;
 mod market_face;
+mod mini_app;
 mod red_bag;
 mod reply;
 mod text;
@@ -40,6 +44,7 @@ pub enum RQElem {
     Dice(market_face::Dice),
     FingerGuessing(market_face::FingerGuessing),
     LightApp(light_app::LightApp),
+    MiniApp(mini_app::MiniApp),
     RedBag(red_bag::RedBag),
     FriendImage(friend_image::FriendImage),
     GroupImage(group_image::GroupImage),
@@ -93,7 +98,13 @@ impl From<msg::elem::Elem> for RQElem {
                     RQElem::MarketFace(f)
                 }
             }
-            msg::elem::Elem::LightApp(e) => RQElem::LightApp(light_app::LightApp::from(e)),
+            msg::elem::Elem::LightApp(e) => {
+                let light_app = light_app::LightApp::from(e);
+                match mini_app::MiniApp::from_light_app(&light_app) {
+                    Some(mini_app) => RQElem::MiniApp(mini_app),
+                    None => RQElem::LightApp(light_app),
+                }
+            }
             msg::elem::Elem::QqWalletMsg(e) => RQElem::RedBag(red_bag::RedBag::from(e)),
             msg::elem::Elem::NotOnlineImage(e) => {
                 RQElem::FriendImage(friend_image::FriendImage::from(e))
@@ -113,6 +124,7 @@ impl fmt::Display for RQElem {
             RQElem::GroupImage(e) => fmt::Display::fmt(e, f),
             RQElem::FriendImage(e) => fmt::Display::fmt(e, f),
             RQElem::FlashImage(e) => fmt::Display::fmt(e, f),
+            RQElem::MiniApp(e) => fmt::Display::fmt(e, f),
             _ => write!(f, ""),
         }
     }
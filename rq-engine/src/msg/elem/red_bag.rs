@@ -1,22 +1,27 @@
 use crate::pb::msg;
 use crate::pb::msg::QqWalletMsg;
 
+// 服务器可能增加字段，只用于接收
 #[derive(Default, Debug, Clone)]
+#[non_exhaustive]
 pub struct RedBag {
     pub msg_type: i32,
     pub title: String,
+    pub summary: String,
+    pub action: String,
+    pub icon_url: String,
 }
 
 impl From<msg::QqWalletMsg> for RedBag {
     fn from(e: QqWalletMsg) -> Self {
         let aio_body = e.aio_body.unwrap_or_default();
+        let receiver = aio_body.receiver.clone().unwrap_or_default();
         Self {
             msg_type: aio_body.msg_type(),
-            title: aio_body
-                .receiver
-                .unwrap_or_default()
-                .title
-                .unwrap_or_default(),
+            title: receiver.title().to_string(),
+            summary: receiver.subtitle().to_string(),
+            action: String::from_utf8_lossy(receiver.link_url()).to_string(),
+            icon_url: String::from_utf8_lossy(receiver.icon_url()).to_string(),
         }
     }
 }
@@ -8,7 +8,7 @@ use crate::pb::msg;
 pub mod elem;
 mod fragment;
 
-#[derive(Debug, Default, Clone)]
+#[derive(Default, Clone)]
 pub struct MessageChain(pub Vec<msg::elem::Elem>);
 
 impl MessageChain {
@@ -50,6 +50,74 @@ impl MessageChain {
         let index = if self.anonymous().is_some() { 1 } else { 0 };
         self.0.insert(index, msg::elem::Elem::from(reply))
     }
+
+    pub fn push_mini_app(&mut self, mini_app: MiniApp) {
+        self.push(mini_app)
+    }
+
+    /// 在消息开头（匿名和回复之后）at 发送者，用于回复消息
+    pub fn with_at_sender(mut self, sender_uin: i64, sender_name: &str) -> Self {
+        let index = self.0.iter().take_while(|e| is_meta_elem(e)).count();
+        let at: Vec<msg::elem::Elem> = At {
+            target: sender_uin,
+            display: format!("@{}", sender_name),
+        }
+        .into();
+        self.0.splice(index..index, at);
+        self
+    }
+
+    /// 遍历元素，不包括匿名和回复
+    pub fn iter(&self) -> impl Iterator<Item = RQElem> + '_ {
+        self.0
+            .iter()
+            .filter(|e| !is_meta_elem(e))
+            .map(|e| RQElem::from(e.clone()))
+    }
+
+    /// 元素数量，不包括匿名和回复
+    pub fn len(&self) -> usize {
+        self.0.iter().filter(|e| !is_meta_elem(e)).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 按顺序拼接所有文本，忽略其他元素
+    pub fn plain_text(&self) -> String {
+        self.iter()
+            .filter_map(|e| match e {
+                RQElem::Text(text) => Some(text.content),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// 是否包含图片（包括闪照）
+    pub fn has_image(&self) -> bool {
+        self.iter().any(|e| {
+            matches!(
+                e,
+                RQElem::GroupImage(_) | RQElem::FriendImage(_) | RQElem::FlashImage(_)
+            )
+        })
+    }
+
+    /// 是否 at 了某人，uin 为 0 表示 at 全体成员
+    pub fn has_at(&self, uin: i64) -> bool {
+        self.at_targets().contains(&uin)
+    }
+
+    /// 所有被 at 的 uin，at 全体成员为 0
+    pub fn at_targets(&self) -> Vec<i64> {
+        self.iter()
+            .filter_map(|e| match e {
+                RQElem::At(at) => Some(at.target),
+                _ => None,
+            })
+            .collect()
+    }
 }
 
 impl IntoIterator for MessageChain {
@@ -59,15 +127,28 @@ impl IntoIterator for MessageChain {
     fn into_iter(self) -> Self::IntoIter {
         self.0
             .into_iter()
-            .filter_map(|e| match e {
-                msg::elem::Elem::SrcMsg(_) => None,
-                msg::elem::Elem::AnonGroupMsg(_) => None,
-                _ => Some(e),
-            })
+            .filter(|e| !is_meta_elem(e))
             .map(RQElem::from)
     }
 }
 
+impl<'a> IntoIterator for &'a MessageChain {
+    type Item = RQElem;
+    type IntoIter = impl Iterator<Item = RQElem> + 'a;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// 匿名和回复不作为消息内容
+fn is_meta_elem(e: &msg::elem::Elem) -> bool {
+    matches!(
+        e,
+        msg::elem::Elem::SrcMsg(_) | msg::elem::Elem::AnonGroupMsg(_)
+    )
+}
+
 impl From<Vec<msg::Elem>> for MessageChain {
     fn from(elements: Vec<msg::Elem>) -> Self {
         Self(elements.into_iter().filter_map(|e| e.elem).collect())
@@ -82,15 +163,39 @@ impl From<MessageChain> for Vec<msg::Elem> {
     }
 }
 
+// 转换成可读文本，图片等元素用 [图片] 之类的占位符代替
 impl fmt::Display for MessageChain {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for x in self.clone().into_iter() {
-            fmt::Display::fmt(&x, f)?
+        for x in self {
+            match x {
+                RQElem::Text(e) => write!(f, "{}", e.content)?,
+                RQElem::At(e) => write!(f, "{}", e.display)?,
+                RQElem::Face(e) => write!(f, "[{}]", e.name)?,
+                RQElem::MarketFace(e) => write!(f, "{}", e.name)?,
+                RQElem::Dice(_) => write!(f, "[骰子]")?,
+                RQElem::FingerGuessing(_) => write!(f, "[猜拳]")?,
+                RQElem::GroupImage(_) | RQElem::FriendImage(_) => write!(f, "[图片]")?,
+                RQElem::FlashImage(_) => write!(f, "[闪照]")?,
+                RQElem::LightApp(_) => write!(f, "[卡片]")?,
+                RQElem::MiniApp(e) => fmt::Display::fmt(&e, f)?,
+                RQElem::RedBag(_) => write!(f, "[红包]")?,
+                RQElem::Other(_) => {}
+            }
         }
         Ok(())
     }
 }
 
+impl fmt::Debug for MessageChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MessageChain")
+            .field("anonymous", &self.anonymous())
+            .field("reply", &self.reply())
+            .field("elements", &self.iter().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,6 +209,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_plain_text() {
+        let mut chain = MessageChain::default();
+        chain.push(At::new(12345));
+        chain.push(Text::new("hello ".into()));
+        chain.push(Face::new(1));
+        chain.push(Text::new("world".into()));
+        assert_eq!(chain.plain_text(), "hello world");
+        assert_eq!(chain.at_targets(), vec![12345]);
+        assert!(chain.has_at(12345));
+        assert!(!chain.has_at(0));
+        assert!(!chain.has_image());
+    }
+
+    #[test]
+    fn test_with_at_sender() {
+        let mut chain = MessageChain::default();
+        chain.with_reply(Reply::default());
+        chain.push(Text::new("hello".into()));
+        let chain = chain.with_at_sender(12345, "bob");
+        assert!(chain.reply().is_some());
+        assert_eq!(chain.at_targets(), vec![12345]);
+        assert_eq!(chain.to_string(), "@bobhello");
+    }
+
+    #[test]
+    fn test_display_placeholder() {
+        let mut chain = MessageChain::default();
+        chain.push(Text::new("hi ".into()));
+        chain.push(Face::new(14));
+        chain.push(Dice::new(1));
+        assert_eq!(chain.to_string(), "hi [微笑][骰子]");
+    }
+
+    #[test]
+    fn test_len() {
+        let mut chain = MessageChain::default();
+        assert!(chain.is_empty());
+        chain.with_reply(Reply::default());
+        chain.push(Text::new("hello".into()));
+        assert_eq!(chain.len(), 1);
+        assert_eq!((&chain).into_iter().count(), 1);
+    }
+
     #[test]
     fn test_display() {
         let mut chain = MessageChain::default();
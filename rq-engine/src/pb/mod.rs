@@ -22,6 +22,10 @@ pub mod cmd0x6ff {
     include!(concat!(env!("OUT_DIR"), "/cmd0x6ff.rs"));
 }
 
+pub mod longmsg {
+    include!(concat!(env!("OUT_DIR"), "/longmsg.rs"));
+}
+
 pub mod msf {
     include!(concat!(env!("OUT_DIR"), "/msf.rs"));
 }
@@ -34,6 +38,10 @@ pub mod msgtype0x210 {
     include!(concat!(env!("OUT_DIR"), "/msgtype0x210.rs"));
 }
 
+pub mod multimsg {
+    include!(concat!(env!("OUT_DIR"), "/multimsg.rs"));
+}
+
 pub mod notify {
     include!(concat!(env!("OUT_DIR"), "/notify.rs"));
 }
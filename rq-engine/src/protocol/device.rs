@@ -1,12 +1,13 @@
 use bytes::Bytes;
 use rand::distributions::DistString;
 use rand::{distributions::Alphanumeric, Rng, RngCore};
-use serde::{Deserialize, Serialize};
 
 use crate::hex::encode_hex;
+use crate::{RQError, RQResult};
 
 //系统版本
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OSVersion {
     pub incremental: String,
     pub release: String,
@@ -26,7 +27,8 @@ impl Default for OSVersion {
 }
 
 //手机设备信息
-#[derive(Default, Serialize, Deserialize, Debug, Clone)]
+#[derive(Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Device {
     pub display: String,
     pub product: String,
@@ -94,6 +96,20 @@ impl Device {
         }
     }
 
+    // 检查登录必需的字段
+    pub fn validate(&self) -> RQResult<()> {
+        if self.imei.is_empty() {
+            return Err(RQError::Other("device imei is empty".into()));
+        }
+        if self.android_id.is_empty() {
+            return Err(RQError::Other("device android_id is empty".into()));
+        }
+        if self.mac_address.is_empty() {
+            return Err(RQError::Other("device mac_address is empty".into()));
+        }
+        Ok(())
+    }
+
     pub fn ksid(&self) -> Bytes {
         Bytes::from(
             format!("|{}|A8.2.7.27f6ea96", self.imei)
@@ -2,7 +2,7 @@ use bytes::{Buf, BufMut, Bytes, BytesMut};
 use rand::Rng;
 
 use crate::binary::BinaryWriter;
-use crate::crypto::{qqtea_decrypt, EncryptECDH};
+use crate::crypto::{qqtea_decrypt_checked, EncryptECDH};
 use crate::{RQError, RQResult};
 
 #[derive(Debug, derivative::Derivative)]
@@ -13,6 +13,25 @@ pub enum EncryptionMethod {
     ST,
 }
 
+// 收到的 oicq 包头中的加密方式
+// EcdhShareKey 使用 ecdh 协商的 share key，SessionTicket 使用 wt_session_ticket_key
+// 目前协议中只见到 0 和 3 两种，其他值（包括旧版 RC4 加密）返回 UnknownEncryptType
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OicqEncryptionType {
+    EcdhShareKey,
+    SessionTicket,
+}
+
+impl OicqEncryptionType {
+    pub fn from_u8(v: u8) -> RQResult<Self> {
+        match v {
+            0 => Ok(Self::EcdhShareKey),
+            3 => Ok(Self::SessionTicket),
+            _ => Err(RQError::UnknownEncryptType),
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct Message {
     pub uin: u32,
@@ -21,6 +40,7 @@ pub struct Message {
     pub encryption_method: EncryptionMethod,
 }
 
+#[derive(Clone)]
 pub struct Codec {
     pub ecdh: EncryptECDH,
     pub random_key: Bytes,
@@ -82,10 +102,21 @@ impl Codec {
         w.freeze()
     }
 
+    fn decrypt_key(&self, encrypt_type: OicqEncryptionType) -> &[u8] {
+        match encrypt_type {
+            OicqEncryptionType::EcdhShareKey => &self.ecdh.initial_share_key,
+            OicqEncryptionType::SessionTicket => &self.wt_session_ticket_key,
+        }
+    }
+
+    // 包头 16 字节，结尾 1 字节，中间为 tea 加密数据，解密时同时校验填充
     pub fn decode<B>(&self, mut reader: B) -> RQResult<Message>
     where
         B: Buf,
     {
+        if reader.remaining() < 17 {
+            return Err(RQError::Decode("oicq packet too short".into()));
+        }
         let flag = reader.get_u8();
         if flag != 2 {
             return Err(RQError::UnknownFlag(flag));
@@ -97,21 +128,13 @@ impl Codec {
         reader.get_u16(); // 1
         m.uin = reader.get_i32() as u32;
         reader.get_u8();
-        let encrypt_type = reader.get_u8();
+        let encrypt_type = OicqEncryptionType::from_u8(reader.get_u8())?;
         reader.get_u8();
-        match encrypt_type {
-            0 => {
-                let len = reader.remaining() - 1;
-                let d = reader.copy_to_bytes(len);
-                m.body = Bytes::from(qqtea_decrypt(&d, &self.ecdh.initial_share_key));
-            }
-            3 => {
-                let len = reader.remaining() - 1;
-                let d = reader.copy_to_bytes(len);
-                m.body = Bytes::from(qqtea_decrypt(&d, &self.wt_session_ticket_key));
-            }
-            _ => return Err(RQError::UnknownEncryptType),
-        }
+        let len = reader.remaining() - 1;
+        let d = reader.copy_to_bytes(len);
+        m.body = qqtea_decrypt_checked(&d, self.decrypt_key(encrypt_type))
+            .map(Bytes::from)
+            .ok_or_else(|| RQError::Decode("oicq checksum mismatch".into()))?;
         Ok(m)
     }
 }
@@ -54,6 +54,14 @@ impl EncryptType {
     }
 }
 
+// 未加密的包头
+#[derive(Default, Debug)]
+pub struct PacketHeader {
+    pub packet_type: PacketType,
+    pub encrypt_type: EncryptType,
+    pub uin: i64,
+}
+
 #[derive(Default, Debug)]
 pub struct Packet {
     pub packet_type: PacketType,
@@ -66,8 +74,9 @@ pub struct Packet {
 }
 
 impl Packet {
+    // 部分响应的大小写和请求不一致，不区分大小写比较
     pub fn check_command_name(self, command_name: &str) -> RQResult<Self> {
-        if self.command_name != command_name {
+        if !self.command_name.eq_ignore_ascii_case(command_name) {
             Err(RQError::CommandNameMismatch(
                 command_name.to_owned(),
                 self.command_name,
@@ -76,4 +85,32 @@ impl Packet {
             Ok(self)
         }
     }
+
+    pub fn body_len(&self) -> usize {
+        self.body.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.body.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_command_name_ignore_case() {
+        let pkt = Packet {
+            command_name: "GroupMgr.getGroupInfo".into(),
+            ..Default::default()
+        };
+        assert!(pkt.check_command_name("GroupMgr.GetGroupInfo").is_ok());
+
+        let pkt = Packet {
+            command_name: "GroupMgr.GetGroupInfo".into(),
+            ..Default::default()
+        };
+        assert!(pkt.check_command_name("GroupMgr.SetGroupInfo").is_err());
+    }
 }
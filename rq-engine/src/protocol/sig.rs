@@ -4,7 +4,7 @@ use bytes::Bytes;
 
 use crate::protocol::device::Device;
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct Sig {
     pub login_bitmap: u64,
     pub tgt: Bytes,
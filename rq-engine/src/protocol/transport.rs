@@ -5,15 +5,17 @@ use flate2::read::ZlibDecoder;
 
 use crate::binary::{BinaryReader, BinaryWriter};
 use crate::command::common::PbToBytes;
+use crate::commands;
 use crate::crypto::{qqtea_decrypt, qqtea_encrypt};
 use crate::protocol::{
     device::Device,
-    packet::{EncryptType, Packet, PacketType},
+    packet::{EncryptType, Packet, PacketHeader, PacketType},
     sig::Sig,
     version::Version,
 };
 use crate::{oicq, pb, RQError, RQResult};
 
+#[derive(Clone)]
 pub struct Transport {
     pub sig: Sig,
     pub device: Device,
@@ -38,9 +40,21 @@ impl Transport {
             pkt.encrypt_type = EncryptType::EmptyKey
         }
 
-        let mut w = BytesMut::new();
-        // let pos = w.len();
-        // w.put_u32(0);
+        // 先序列化并加密 body，得到长度后一次性分配整个包，避免扩容
+        let mut w2 = BytesMut::with_capacity(pkt.body.len() + pkt.command_name.len() + 128);
+        self.encode_body(&pkt, &mut w2);
+        let body = match pkt.encrypt_type {
+            EncryptType::D2Key => Bytes::from(qqtea_encrypt(&w2, &self.sig.d2key)),
+            EncryptType::EmptyKey => Bytes::from(qqtea_encrypt(&w2, &[0; 16])),
+            EncryptType::NoEncrypt => w2.freeze(),
+        };
+
+        let uin = pkt.uin.to_string();
+        let key_len = match (&pkt.packet_type, &pkt.encrypt_type) {
+            (PacketType::Login, EncryptType::D2Key) => self.sig.d2.len(),
+            _ => 0,
+        };
+        let mut w = BytesMut::with_capacity(4 + 1 + 4 + key_len + 1 + 4 + uin.len() + body.len());
 
         // vvv w.Write(head) vvv
         w.put_u32(pkt.packet_type.value());
@@ -56,40 +70,57 @@ impl Transport {
             },
         }
         w.put_u8(0x00);
-        w.write_string(&pkt.uin.to_string());
+        w.write_string(&uin);
         // ^^^ w.Write(head) ^^^
 
-        let mut w2 = BytesMut::new();
-        self.encode_body(&pkt, &mut w2);
-        let mut body = w2.freeze();
-        match pkt.encrypt_type {
-            EncryptType::D2Key => {
-                body = Bytes::from(qqtea_encrypt(&body, &self.sig.d2key));
-            }
-            EncryptType::EmptyKey => {
-                body = Bytes::from(qqtea_encrypt(&body, &[0; 16]));
-            }
-            EncryptType::NoEncrypt => {}
-        }
         w.put_slice(&body);
-
-        // let len = w.len();
-        // w[pos..pos + 4].as_mut().put_u32(len as u32);
         w.freeze()
     }
 
     pub fn decode_packet<B>(&self, mut r: B) -> RQResult<Packet>
+    where
+        B: Buf,
+    {
+        let header = self.decode_packet_header(&mut r)?;
+        self.decode_packet_body(header, r)
+    }
+
+    // 只解析包头，packet_type 或 encrypt_type 不合法时可以提前丢弃
+    pub fn decode_packet_header<B>(&self, r: &mut B) -> RQResult<PacketHeader>
+    where
+        B: Buf,
+    {
+        // packet_type(4) + encrypt_type(1) + 0x00(1) + uin 长度(4)
+        if r.remaining() < 10 {
+            return Err(RQError::PacketDropped);
+        }
+        let packet_type = PacketType::from_i32(r.get_i32())?;
+        let encrypt_type = EncryptType::from_u8(r.get_u8())?;
+        r.get_u8(); // 0x00
+        let uin_len = r.get_i32() as usize;
+        if uin_len < 4 || uin_len - 4 > r.remaining() {
+            return Err(RQError::PacketDropped);
+        }
+        let uin = String::from_utf8_lossy(&r.copy_to_bytes(uin_len - 4))
+            .parse()
+            .unwrap_or_default();
+        Ok(PacketHeader {
+            packet_type,
+            encrypt_type,
+            uin,
+        })
+    }
+
+    pub fn decode_packet_body<B>(&self, header: PacketHeader, r: B) -> RQResult<Packet>
     where
         B: Buf,
     {
         let mut pkt = Packet {
-            packet_type: PacketType::from_i32(r.get_i32())?,
-            encrypt_type: EncryptType::from_u8(r.get_u8())?,
+            packet_type: header.packet_type,
+            encrypt_type: header.encrypt_type,
+            uin: header.uin,
             ..Default::default()
         };
-        r.get_u8(); // 0x00
-
-        pkt.uin = r.read_string().parse().unwrap_or_default();
 
         let mut body = Bytes::from(r.chunk().to_owned());
         match pkt.encrypt_type {
@@ -165,7 +196,7 @@ impl Transport {
         }
         pkt.message = head.read_string();
         pkt.command_name = head.read_string();
-        if &pkt.command_name == "Heartbeat.Alive" {
+        if &pkt.command_name == commands::HEARTBEAT_ALIVE {
             return Ok(());
         }
 
@@ -205,3 +236,40 @@ impl Transport {
         .to_bytes()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::device::Device;
+    use crate::protocol::version::ANDROID_WATCH;
+
+    #[test]
+    fn test_decode_packet_header() {
+        let transport = Transport::new(Device::random(), ANDROID_WATCH);
+        let mut buf = BytesMut::new();
+        buf.put_i32(0x0B);
+        buf.put_u8(0x01);
+        buf.put_u8(0x00);
+        buf.put_i32(4 + 5);
+        buf.put_slice(b"12345");
+        let header = transport
+            .decode_packet_header(&mut buf.clone().freeze())
+            .unwrap();
+        assert_eq!(header.packet_type, PacketType::Simple);
+        assert_eq!(header.encrypt_type, EncryptType::D2Key);
+        assert_eq!(header.uin, 12345);
+
+        // uin 被截断
+        let mut truncated = buf.freeze().slice(..12);
+        assert!(matches!(
+            transport.decode_packet_header(&mut truncated),
+            Err(RQError::PacketDropped)
+        ));
+        // 连 packet_type 都不完整
+        let mut truncated = Bytes::from_static(&[0x00, 0x00]);
+        assert!(matches!(
+            transport.decode_packet_header(&mut truncated),
+            Err(RQError::PacketDropped)
+        ));
+    }
+}
@@ -11,6 +11,41 @@ pub enum Protocol {
     QiDian,
 }
 
+// 各协议支持的功能
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProtocolCapabilities {
+    pub has_qrcode_login: bool,
+    // 群文件，手表协议没有
+    pub has_group_files: bool,
+}
+
+impl Protocol {
+    pub fn capabilities(&self) -> ProtocolCapabilities {
+        match self {
+            Protocol::IPad => ProtocolCapabilities {
+                has_qrcode_login: true,
+                has_group_files: true,
+            },
+            Protocol::AndroidPhone => ProtocolCapabilities {
+                has_qrcode_login: false,
+                has_group_files: true,
+            },
+            Protocol::AndroidWatch => ProtocolCapabilities {
+                has_qrcode_login: true,
+                has_group_files: false,
+            },
+            Protocol::MacOS => ProtocolCapabilities {
+                has_qrcode_login: true,
+                has_group_files: true,
+            },
+            Protocol::QiDian => ProtocolCapabilities {
+                has_qrcode_login: false,
+                has_group_files: true,
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Version {
     pub apk_sign: &'static [u8],
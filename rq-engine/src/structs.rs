@@ -1,7 +1,11 @@
-use bytes::Bytes;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 
-pub use crate::command::oidb_svc::ProfileDetailUpdate;
+use bytes::{Buf, Bytes};
+
+pub use crate::command::oidb_svc::{Gender, ProfileDetailUpdate};
 pub use crate::command::stat_svc::{CustomOnlineStatus, ExtOnlineStatus, OnlineStatus, Status};
+use crate::msg::elem::At;
 use crate::msg::MessageChain;
 use crate::{jce, pb};
 
@@ -42,12 +46,17 @@ pub struct BigDataReqSessionInfo {
     pub session_key: Bytes,
 }
 
-#[derive(Debug, Default)]
+// 字段缺失时使用默认值，旧版本保存的数据也可以反序列化
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct GroupInfo {
     pub uin: i64,
     pub code: i64,
     pub name: String,
     pub memo: String,
+    // 群简介
+    pub notice: String,
     pub owner_uin: i64,
     pub group_create_time: u32,
     pub group_level: u32,
@@ -61,7 +70,10 @@ pub struct GroupInfo {
     pub last_msg_seq: i64,
 }
 
+// 相等和哈希只比较 (group_code, uin)
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct GroupMemberInfo {
     pub group_code: i64,
     pub uin: i64,
@@ -75,9 +87,82 @@ pub struct GroupMemberInfo {
     pub special_title_expire_time: i64,
     pub shut_up_timestamp: i64,
     pub permission: GroupMemberPermission,
+    // 群荣誉（龙王、群聊之火等）
+    pub honor: Vec<HonourType>,
+}
+
+impl GroupMemberInfo {
+    pub fn id(&self) -> GroupMemberId {
+        GroupMemberId(self.group_code, self.uin)
+    }
+}
+
+impl PartialEq for GroupMemberInfo {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+    }
+}
+
+impl Eq for GroupMemberInfo {}
+
+impl Hash for GroupMemberInfo {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id().hash(state)
+    }
+}
+
+// 群成员唯一标识 (group_code, uin)，可以作为 HashMap 的 key
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GroupMemberId(pub i64, pub i64);
+
+/// 群荣誉类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HonourType {
+    // 龙王
+    Talkative,
+    // 群聊之火
+    Performer,
+    // 群聊炽焰
+    Legend,
+    // 冒尖小春笋
+    StrongNewbie,
+    // 快乐源泉
+    Emotion,
+    // 热心市民
+    Enthusiastic,
+    Unknown(u64),
+}
+
+impl HonourType {
+    /// 根据灰条消息获取荣誉类型，templ_id 未知时根据内容中的荣誉名称判断
+    pub fn from_gray_tip(templ_id: u64, content: &str) -> Self {
+        match templ_id {
+            1052 => HonourType::Performer,
+            1053 | 1054 => HonourType::Talkative,
+            1067 => HonourType::Emotion,
+            _ => HonourType::from_name(content).unwrap_or(HonourType::Unknown(templ_id)),
+        }
+    }
+
+    // 根据荣誉名称判断
+    fn from_name(content: &str) -> Option<Self> {
+        [
+            ("群聊炽焰", HonourType::Legend),
+            ("群聊之火", HonourType::Performer),
+            ("龙王", HonourType::Talkative),
+            ("冒尖小春笋", HonourType::StrongNewbie),
+            ("快乐源泉", HonourType::Emotion),
+            ("热心市民", HonourType::Enthusiastic),
+        ]
+        .into_iter()
+        .find(|(name, _)| content.contains(name))
+        .map(|(_, t)| t)
+    }
 }
 
 #[derive(Debug, Clone, derivative::Derivative)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derivative(Default)]
 pub enum GroupMemberPermission {
     Owner = 1,
@@ -86,12 +171,36 @@ pub enum GroupMemberPermission {
     Member = 3,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct FriendInfo {
     pub uin: i64,
     pub nick: String,
+    // 备注，来自 friendlist.getFriendGroupList
     pub remark: String,
     pub face_id: i16,
+    // 所在好友分组
+    pub group_id: u8,
+}
+
+// 好友分组
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FriendGroup {
+    pub id: u8,
+    pub name: String,
+    pub member_count: i32,
+}
+
+impl FriendInfo {
+    /// 有备注时返回备注，否则返回昵称
+    pub fn display_name(&self) -> &str {
+        if self.remark.is_empty() {
+            &self.nick
+        } else {
+            &self.remark
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -101,11 +210,34 @@ pub struct SummaryCardInfo {
     pub age: u8,
     pub nickname: String,
     pub level: i32,
+    pub country: String,
+    pub province: String,
     pub city: String,
     pub sign: String,
+    // 签名修改时间
+    pub sign_date: Option<i64>,
     pub mobile: String,
     pub login_days: i64,
     pub q_id: String,
+    // 学校
+    pub college: String,
+    // 家乡
+    pub hometown: String,
+    // 职业
+    pub profession: String,
+    pub company: String,
+    // 个性标签
+    pub personal_tags: Vec<String>,
+    // 资料卡上展示的徽章
+    pub badges: Vec<Badge>,
+}
+
+// 资料卡徽章
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Badge {
+    pub id: i32,
+    pub name: String,
+    pub level: i32,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -123,12 +255,38 @@ pub struct PrivateMessage {
 pub struct GroupMessage {
     pub seqs: Vec<i32>,
     pub rands: Vec<i32>,
+    // 服务端分配的消息唯一 id，可用于去重
+    pub msg_uid: i64,
     pub group_code: i64,
     pub from_uin: i64,
     pub time: i32,
     pub elements: MessageChain,
 }
 
+impl GroupMessage {
+    /// 是否 at 了自己或全体成员
+    pub fn is_at_me(&self, self_uin: i64) -> bool {
+        self.elements.has_at(self_uin) || self.elements.has_at(0)
+    }
+
+    /// at 自己之后紧跟的文本，去掉开头空白，常用于解析指令
+    pub fn get_text_after_at_me(&self, self_uin: i64) -> Option<&str> {
+        let mut elems = self.elements.0.iter();
+        elems.find(|e| match e {
+            pb::msg::elem::Elem::Text(text) if !text.attr6_buf().is_empty() => {
+                At::from(text.clone()).target == self_uin
+            }
+            _ => false,
+        })?;
+        match elems.next()? {
+            pb::msg::elem::Elem::Text(text) if text.attr6_buf().is_empty() => {
+                Some(text.str().trim_start())
+            }
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct TempMessage {
     pub seqs: Vec<i32>,
@@ -146,6 +304,53 @@ pub struct TempMessage {
 pub struct NewMember {
     pub group_code: i64,
     pub member_uin: i64,
+    pub join_method: JoinMethod,
+}
+
+// 入群方式
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum JoinMethod {
+    // 通过链接或二维码直接加入
+    ByLink,
+    ByInvitation {
+        inviter_uin: i64,
+    },
+    // 申请后由管理员同意
+    ByApproval {
+        approver_uin: i64,
+    },
+    #[default]
+    Unknown,
+}
+
+impl JoinMethod {
+    // msg_type 33 的 msg_content: group_uin(4) flag(1) member_uin(4) join_type(1) operator_uin(4)
+    pub fn from_join_content(mut content: &[u8]) -> Self {
+        if content.len() < 14 {
+            return JoinMethod::Unknown;
+        }
+        content.advance(9);
+        let join_type = content.get_u8();
+        let operator_uin = content.get_u32() as i64;
+        match join_type {
+            0x02 | 0x82 if operator_uin == 0 => JoinMethod::ByLink,
+            0x02 | 0x82 => JoinMethod::ByApproval {
+                approver_uin: operator_uin,
+            },
+            0x03 | 0x83 => JoinMethod::ByInvitation {
+                inviter_uin: operator_uin,
+            },
+            _ => JoinMethod::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GroupMemberCardChanged {
+    pub group_code: i64,
+    pub member_uin: i64,
+    pub old_card: String,
+    pub new_card: String,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -156,6 +361,15 @@ pub struct GroupMute {
     pub time: u32,
 }
 
+#[derive(Debug, Clone)]
+pub struct GroupHonorChanged {
+    pub group_code: i64,
+    pub member_uin: i64,
+    pub honour_type: HonourType,
+    // true: 获得, false: 失去
+    pub is_gained: bool,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct FriendMessageRecall {
     pub msg_seq: i32,
@@ -176,6 +390,8 @@ pub struct GroupMessageRecall {
 pub struct GroupLeave {
     pub group_code: i64,
     pub member_uin: i64,
+    // 被踢时为 true，operator_uin 为踢人的管理员
+    pub was_kicked: bool,
     pub operator_uin: Option<i64>,
 }
 
@@ -183,6 +399,51 @@ pub struct GroupLeave {
 pub struct FriendPoke {
     pub sender: i64,
     pub receiver: i64,
+    // 例如 "戳了戳"
+    pub action_text: String,
+    // 例如 "的小屁股"
+    pub suffix: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GroupPoke {
+    pub group_code: i64,
+    pub sender: i64,
+    pub receiver: i64,
+    // 例如 "戳了戳"
+    pub action_text: String,
+    // 例如 "的脸"
+    pub suffix: String,
+}
+
+// 群内抛硬币
+#[derive(Debug, Clone, Default)]
+pub struct GroupToss {
+    pub group_code: i64,
+    pub operator: i64,
+    // 例如 "正面"
+    pub result: String,
+}
+
+// 群内送礼物
+#[derive(Debug, Clone, Default)]
+pub struct GroupGift {
+    pub group_code: i64,
+    pub sender: i64,
+    pub receiver: i64,
+    // 例如 "一个棒棒糖"
+    pub gift_name: String,
+}
+
+// 未解析的群灰条提示，可以根据 busi_id 和 params 自行处理
+#[derive(Debug, Clone, Default)]
+pub struct GroupTips {
+    pub group_code: i64,
+    pub busi_type: u64,
+    pub busi_id: u64,
+    pub templ_id: u64,
+    pub params: HashMap<String, String>,
+    pub content: String,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -204,6 +465,13 @@ pub struct MemberPermissionChange {
     pub new_permission: GroupMemberPermission,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct GroupOwnerChange {
+    pub group_code: i64,
+    pub old_owner_uin: i64,
+    pub new_owner_uin: i64,
+}
+
 // 用于撤回
 #[derive(Debug, Clone, Default)]
 pub struct MessageReceipt {
@@ -212,6 +480,25 @@ pub struct MessageReceipt {
     pub time: i64,
 }
 
+/// 已发送的好友消息，用于撤回和回复
+#[derive(Debug, Clone, Default)]
+pub struct SentMessageInfo {
+    pub msg_seq: i32,
+    pub msg_uid: i64,
+    pub msg_time: i64,
+}
+
+impl From<&MessageReceipt> for SentMessageInfo {
+    fn from(receipt: &MessageReceipt) -> Self {
+        let rand = receipt.rands.first().copied().unwrap_or_default();
+        Self {
+            msg_seq: receipt.seqs.first().copied().unwrap_or_default(),
+            msg_uid: 0x0100_0000_0000_0000 | (rand as i64 & 0xFFFF_FFFF),
+            msg_time: receipt.time,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct GroupAudio(pub pb::msg::Ptt);
 
@@ -238,3 +525,48 @@ pub struct PrivateAudioMessage {
     pub from_nick: String,
     pub audio: PrivateAudio,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_missing_fields() {
+        let friend: FriendInfo = serde_json::from_str(r#"{"uin":1,"nick":"a"}"#).unwrap();
+        assert_eq!(friend.uin, 1);
+        assert_eq!(friend.group_id, 0);
+
+        let member = GroupMemberInfo {
+            uin: 2,
+            permission: GroupMemberPermission::Administrator,
+            honor: vec![HonourType::Talkative],
+            ..Default::default()
+        };
+        let member: GroupMemberInfo =
+            serde_json::from_str(&serde_json::to_string(&member).unwrap()).unwrap();
+        assert_eq!(member.uin, 2);
+        assert_eq!(member.honor, vec![HonourType::Talkative]);
+    }
+
+    #[test]
+    fn test_honour_type_from_gray_tip() {
+        assert_eq!(HonourType::from_gray_tip(1052, ""), HonourType::Performer);
+        assert_eq!(
+            HonourType::from_gray_tip(0, "恭喜 a 获得群聊炽焰"),
+            HonourType::Legend
+        );
+        assert_eq!(
+            HonourType::from_gray_tip(0, "恭喜 a 获得冒尖小春笋"),
+            HonourType::StrongNewbie
+        );
+        assert_eq!(
+            HonourType::from_gray_tip(0, "恭喜 a 成为热心市民"),
+            HonourType::Enthusiastic
+        );
+        assert_eq!(
+            HonourType::from_gray_tip(1, "hello"),
+            HonourType::Unknown(1)
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -12,7 +14,7 @@ use crate::engine::msg::elem::FriendImage;
 use crate::engine::msg::MessageChain;
 use crate::engine::pb;
 use crate::engine::structs::PrivateAudio;
-use crate::engine::structs::{FriendInfo, MessageReceipt};
+use crate::engine::structs::{FriendGroup, FriendInfo, MessageReceipt, SentMessageInfo};
 use crate::internal::image_info::ImageInfo;
 use crate::{RQError, RQResult};
 
@@ -114,6 +116,7 @@ impl super::super::Client {
         let mut friends = self.friends.write().await;
         friends.clear();
         friends.extend(friend_list);
+        self.friend_count.store(friends.len(), Ordering::Relaxed);
         Ok(())
     }
 
@@ -122,6 +125,60 @@ impl super::super::Client {
         self.friends.read().await.get(&uin).cloned()
     }
 
+    /// 缓存中所有好友 uin 的快照，之后好友列表变化不会反映到返回值中，不需要 await
+    /// 好友列表正在刷新（写锁被占用）时返回空列表
+    pub fn friend_uins(&self) -> Vec<i64> {
+        self.friends
+            .try_read()
+            .map(|friends| friends.keys().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// 缓存中所有好友的快照，之后好友列表变化不会反映到返回值中
+    pub async fn cached_friends(&self) -> HashMap<i64, Arc<FriendInfo>> {
+        self.friends.read().await.clone()
+    }
+
+    /// 获取好友分组
+    pub async fn get_friend_groups(&self) -> RQResult<Vec<FriendGroup>> {
+        Ok(self.get_friend_list(0, 1, 0, 100).await?.groups)
+    }
+
+    /// 修改好友分组名称
+    pub async fn rename_friend_group(&self, group_id: u8, new_name: &str) -> RQResult<()> {
+        let req = self
+            .engine
+            .read()
+            .await
+            .build_rename_friend_group_packet(group_id, new_name)?;
+        let resp = self.send_and_wait(req).await?;
+        self.engine
+            .read()
+            .await
+            .decode_rename_friend_group_response(resp.body)
+    }
+
+    /// 移动好友到分组
+    pub async fn move_friend_to_group(&self, uin: i64, group_id: u8) -> RQResult<()> {
+        let req = self
+            .engine
+            .read()
+            .await
+            .build_move_friend_to_group_packet(uin, group_id);
+        let resp = self.send_and_wait(req).await?;
+        self.engine
+            .read()
+            .await
+            .decode_move_friend_to_group_response(resp.body)?;
+        let mut friends = self.friends.write().await;
+        if let Some(friend) = friends.get_mut(&uin) {
+            let mut info = friend.as_ref().clone();
+            info.group_id = group_id;
+            *friend = Arc::new(info);
+        }
+        Ok(())
+    }
+
     /// 好友戳一戳
     pub async fn friend_poke(&self, target: i64) -> RQResult<()> {
         let req = self.engine.read().await.build_friend_poke_packet(target);
@@ -139,6 +196,16 @@ impl super::super::Client {
             .await
     }
 
+    /// 发送好友消息，返回 seq、uid 和发送时间
+    pub async fn send_friend_message(
+        &self,
+        target: i64,
+        message_chain: MessageChain,
+    ) -> RQResult<SentMessageInfo> {
+        let receipt = self.send_private_message(target, message_chain).await?;
+        Ok(SentMessageInfo::from(&receipt))
+    }
+
     /// 发送私聊语音
     pub async fn send_private_audio(
         &self,
@@ -169,11 +236,16 @@ impl super::super::Client {
             0,
             0,
         );
-        let _ = self.send_and_wait(req).await?;
+        let resp = self.send_and_wait(req).await?;
+        let send_time = self
+            .engine
+            .read()
+            .await
+            .decode_send_message_response(resp.body)?;
         Ok(MessageReceipt {
             seqs: vec![seq],
             rands: vec![ran],
-            time,
+            time: if send_time > 0 { send_time } else { time },
         })
     }
 
@@ -316,7 +388,7 @@ impl super::super::Client {
             .decode_private_try_up_ptt_resp(resp)?;
         Ok(PrivateAudio(pb::msg::Ptt {
             file_type: Some(4),
-            src_uin: Some(self.uin().await),
+            src_uin: Some(self.uin()),
             file_uuid: Some(uuid),
             file_name: Some(format!("{}.amr", encode_hex(&md5))),
             file_md5: Some(md5),
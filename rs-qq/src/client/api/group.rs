@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -12,12 +14,13 @@ use crate::engine::command::{friendlist::*, oidb_svc::*, profile_service::*};
 use crate::engine::common::group_code2uin;
 use crate::engine::hex::encode_hex;
 use crate::engine::highway::BdhInput;
-use crate::engine::msg::elem::{Anonymous, GroupImage};
+use crate::engine::msg::elem::{Anonymous, GroupImage, LongMessage};
 use crate::engine::msg::MessageChain;
 use crate::engine::pb;
 use crate::engine::structs::GroupAudio;
-use crate::engine::structs::{GroupInfo, GroupMemberInfo, MessageReceipt};
+use crate::engine::structs::{GroupInfo, GroupMemberInfo, GroupMemberPermission, MessageReceipt};
 use crate::internal::image_info::ImageInfo;
+use crate::structs::{EssenceMessage, PollOption, PollResult};
 use crate::{RQError, RQResult};
 
 impl super::super::Client {
@@ -91,13 +94,125 @@ impl super::super::Client {
             .decode_group_list_response(resp.body)
     }
 
-    /// 发送群消息
+    /// 发送群消息，receipt.seqs 是服务器分配的 seq，可直接用于撤回和回复
+    /// 5 秒内没有收到服务器回执时 seq 为 0
     pub async fn send_group_message(
         &self,
         group_code: i64,
         message_chain: MessageChain,
     ) -> RQResult<MessageReceipt> {
-        self._send_group_message(group_code, message_chain, None)
+        let ran = (rand::random::<u32>() >> 1) as i32;
+        self._send_group_message(group_code, message_chain, None, ran, 1)
+            .await
+    }
+
+    /// 发送长消息，超过长度限制时上传到服务器，以长消息卡片的形式发送
+    /// 未超过限制时和 send_group_message 相同
+    pub async fn send_group_long_message(
+        &self,
+        group_code: i64,
+        message_chain: MessageChain,
+    ) -> RQResult<MessageReceipt> {
+        // 单条消息序列化后的长度上限
+        const LONG_MESSAGE_THRESHOLD: usize = 5000;
+        let elems: Vec<pb::msg::Elem> = message_chain.clone().into();
+        let len: usize = elems.iter().map(prost::Message::encoded_len).sum();
+        if len <= LONG_MESSAGE_THRESHOLD {
+            return self.send_group_message(group_code, message_chain).await;
+        }
+        let res_id = self.upload_group_long_message(group_code, elems).await?;
+        let card = LongMessage::new(res_id, message_chain.plain_text());
+        self.send_group_message(group_code, MessageChain::new(card))
+            .await
+    }
+
+    // 上传长消息，返回 resid
+    async fn upload_group_long_message(
+        &self,
+        group_code: i64,
+        elems: Vec<pb::msg::Elem>,
+    ) -> RQResult<String> {
+        let group_uin = group_code2uin(group_code);
+        let group_card = self.account_info.read().await.nickname.clone();
+        let time = chrono::Utc::now().timestamp();
+        let content = self
+            .engine
+            .read()
+            .await
+            .pack_group_long_message(group_code, group_card, elems, time)?;
+        let req = self.engine.read().await.build_multi_apply_up_packet(
+            group_uin,
+            content.len() as i64,
+            md5::compute(&content).to_vec(),
+            1,
+        );
+        let resp = self.send_and_wait(req).await?;
+        let apply_up = self
+            .engine
+            .read()
+            .await
+            .decode_multi_apply_up_response(resp.body)?;
+        let body = self.engine.read().await.build_long_req_body(
+            group_uin,
+            content.to_vec(),
+            apply_up.upload_key,
+        );
+        let mut last_err = RQError::Other("upload_addrs is empty".into());
+        for addr in apply_up.upload_addrs {
+            match self
+                .highway_upload_bdh(
+                    addr,
+                    BdhInput {
+                        command_id: 27,
+                        body: body.clone(),
+                        ticket: apply_up.msg_sig.clone(),
+                        ext: vec![],
+                        encrypt: false,
+                        chunk_size: 256 * 1024,
+                        send_echo: true,
+                    },
+                )
+                .await
+            {
+                Ok(_) => return Ok(apply_up.res_id),
+                Err(err) => last_err = err,
+            }
+        }
+        Err(last_err)
+    }
+
+    /// 向多个群发送同一条消息，最多同时发送 5 个，返回每个群的结果
+    pub async fn broadcast_group_message(
+        &self,
+        group_codes: Vec<i64>,
+        message_chain: MessageChain,
+    ) -> HashMap<i64, RQResult<MessageReceipt>> {
+        stream::iter(group_codes)
+            .map(|group_code| {
+                let message_chain = message_chain.clone();
+                async move {
+                    (
+                        group_code,
+                        self.send_group_message(group_code, message_chain).await,
+                    )
+                }
+            })
+            .buffer_unordered(5)
+            .collect()
+            .await
+    }
+
+    /// 发送群消息，超时后使用相同的 rand 重试
+    /// 重试前会检查是否已经收到这条消息的回执，避免网络不稳定时重复发送
+    pub async fn send_group_message_idempotent(
+        &self,
+        group_code: i64,
+        message_chain: MessageChain,
+        idempotency_key: u64,
+    ) -> RQResult<MessageReceipt> {
+        const MAX_ATTEMPTS: usize = 3;
+        let ran = ((idempotency_key ^ (idempotency_key >> 32)) as u32 >> 1) as i32;
+        self._send_group_message(group_code, message_chain, None, ran, MAX_ATTEMPTS)
             .await
     }
 
@@ -107,8 +222,15 @@ impl super::super::Client {
         group_code: i64,
         group_audio: GroupAudio,
     ) -> RQResult<MessageReceipt> {
-        self._send_group_message(group_code, MessageChain::default(), Some(group_audio.0))
-            .await
+        let ran = (rand::random::<u32>() >> 1) as i32;
+        self._send_group_message(
+            group_code,
+            MessageChain::default(),
+            Some(group_audio.0),
+            ran,
+            1,
+        )
+        .await
     }
 
     async fn _send_group_message(
@@ -116,38 +238,71 @@ impl super::super::Client {
         group_code: i64,
         message_chain: MessageChain,
         group_audio: Option<pb::msg::Ptt>,
+        ran: i32,
+        max_attempts: usize,
     ) -> RQResult<MessageReceipt> {
         let time = chrono::Utc::now().timestamp();
-        let ran = (rand::random::<u32>() >> 1) as i32;
-        let (tx, rx) = tokio::sync::oneshot::channel();
+        let elems: Vec<pb::msg::Elem> = message_chain.into();
+        let (tx, mut rx) = tokio::sync::oneshot::channel();
         {
             self.receipt_waiters.lock().await.insert(ran, tx);
         }
-        let req = self.engine.read().await.build_group_sending_packet(
-            group_code,
-            message_chain.into(),
-            group_audio,
-            ran,
-            time,
-            1,
-            0,
-            0,
-            false,
-        );
-        let _ = self.send_and_wait(req).await?;
+        let mut attempts = 0;
+        let send_time = loop {
+            attempts += 1;
+            let req = self.engine.read().await.build_group_sending_packet(
+                group_code,
+                elems.clone(),
+                group_audio.clone(),
+                ran,
+                time,
+                1,
+                0,
+                0,
+                false,
+            );
+            let result = match self.send_and_wait(req).await {
+                Ok(resp) => self
+                    .engine
+                    .read()
+                    .await
+                    .decode_send_message_response(resp.body),
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(send_time) => break send_time,
+                Err(RQError::Timeout { .. }) if attempts < max_attempts => {
+                    // 已经收到回执说明服务器处理过了，不再重发
+                    if let Ok(seq) = rx.try_recv() {
+                        return Ok(MessageReceipt {
+                            seqs: vec![seq],
+                            rands: vec![ran],
+                            time,
+                        });
+                    }
+                }
+                Err(err) => {
+                    self.receipt_waiters.lock().await.remove(&ran);
+                    return Err(err);
+                }
+            }
+        };
         let mut receipt = MessageReceipt {
             seqs: vec![0],
             rands: vec![ran],
-            time,
+            time: if send_time > 0 { send_time } else { time },
         };
+        // 服务器分配的 seq 在自己的消息推送中返回
         match tokio::time::timeout(Duration::from_secs(5), rx).await {
             Ok(Ok(seq)) => {
                 if let Some(s) = receipt.seqs.first_mut() {
                     *s = seq;
                 }
             }
-            Ok(Err(_)) => {} //todo
-            Err(_) => {}
+            Ok(Err(_)) => {}
+            Err(_) => {
+                self.receipt_waiters.lock().await.remove(&ran);
+            }
         }
         Ok(receipt)
     }
@@ -182,6 +337,79 @@ impl super::super::Client {
         self.groups.read().await.get(&code).cloned()
     }
 
+    /// 缓存中所有群号的快照，之后群列表变化不会反映到返回值中，不需要 await
+    /// 群列表正在刷新（写锁被占用）时返回空列表
+    pub fn group_codes(&self) -> Vec<i64> {
+        self.groups
+            .try_read()
+            .map(|groups| groups.keys().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// 缓存中所有群的快照，之后群列表变化不会反映到返回值中
+    pub async fn cached_groups(&self) -> HashMap<i64, Arc<Group>> {
+        self.groups.read().await.clone()
+    }
+
+    // 不等待锁读取缓存中的群，缓存正在刷新（写锁被占用）时视为不在缓存中
+    fn try_find_cached_group(&self, group_code: i64) -> Option<Arc<Group>> {
+        self.groups.try_read().ok()?.get(&group_code).cloned()
+    }
+
+    /// 缓存中该用户是否是群成员，不发送请求，不需要 await
+    /// 群不在缓存中或缓存正在刷新时返回 false
+    pub fn is_group_member(&self, group_code: i64, uin: i64) -> bool {
+        let group = match self.try_find_cached_group(group_code) {
+            Some(group) => group,
+            None => {
+                tracing::debug!(target: "rs_qq", "group {} is not cached", group_code);
+                return false;
+            }
+        };
+        group
+            .members
+            .try_read()
+            .map(|members| members.iter().any(|m| m.uin == uin))
+            .unwrap_or(false)
+    }
+
+    /// 缓存中的群主，不发送请求，不需要 await
+    /// 群不在缓存中或缓存正在刷新时返回 None
+    pub fn get_group_owner_uin(&self, group_code: i64) -> Option<i64> {
+        let group = self.try_find_cached_group(group_code)?;
+        let owner_uin = group.info.try_read().ok()?.owner_uin;
+        Some(owner_uin)
+    }
+
+    pub fn is_group_owner(&self, group_code: i64, uin: i64) -> bool {
+        self.get_group_owner_uin(group_code) == Some(uin)
+    }
+
+    /// 缓存中的群成员权限，不发送请求，不需要 await
+    /// 群或成员不在缓存中、缓存正在刷新时返回 None
+    pub fn get_member_permission(
+        &self,
+        group_code: i64,
+        uin: i64,
+    ) -> Option<GroupMemberPermission> {
+        let group = self.try_find_cached_group(group_code)?;
+        let permission = group
+            .members
+            .try_read()
+            .ok()?
+            .iter()
+            .find(|m| m.uin == uin)
+            .map(|m| m.permission.clone());
+        permission
+    }
+
+    pub fn is_admin_or_owner(&self, group_code: i64, uin: i64) -> bool {
+        matches!(
+            self.get_member_permission(group_code, uin),
+            Some(GroupMemberPermission::Owner | GroupMemberPermission::Administrator)
+        )
+    }
+
     /// 批量获取群信息
     pub async fn get_group_infos(&self, group_codes: Vec<i64>) -> RQResult<Vec<GroupInfo>> {
         let req = self
@@ -196,6 +424,20 @@ impl super::super::Client {
             .decode_group_info_response(resp.body)
     }
 
+    /// 获取群人数 (当前人数, 最大人数)，不拉取成员列表
+    pub async fn get_group_member_count(&self, group_code: i64) -> RQResult<(u32, u32)> {
+        let req = self
+            .engine
+            .read()
+            .await
+            .build_group_member_count_request_packet(group_code);
+        let resp = self.send_and_wait(req).await?;
+        self.engine
+            .read()
+            .await
+            .decode_group_member_count_response(resp.body)
+    }
+
     /// 获取群信息，请先尝试 find_group
     pub async fn get_group_info(&self, group_code: i64) -> RQResult<Option<GroupInfo>> {
         Ok(self.get_group_infos(vec![group_code]).await?.pop())
@@ -214,10 +456,11 @@ impl super::super::Client {
         groups.insert(
             group_info.code,
             Arc::new(Group {
-                info: group_info,
+                info: RwLock::new(group_info),
                 members: RwLock::new(members),
             }),
         );
+        self.group_count.store(groups.len(), Ordering::Relaxed);
         Ok(())
     }
 
@@ -246,7 +489,7 @@ impl super::super::Client {
                 (
                     g.code,
                     Arc::new(Group {
-                        info: g,
+                        info: RwLock::new(g),
                         members: RwLock::new(mem_list),
                     }),
                 )
@@ -258,6 +501,7 @@ impl super::super::Client {
         let mut groups = self.groups.write().await;
         groups.clear();
         groups.extend(group_list);
+        self.group_count.store(groups.len(), Ordering::Relaxed);
         Ok(())
     }
 
@@ -334,6 +578,35 @@ impl super::super::Client {
         Ok(())
     }
 
+    /// 转让群主，只有群主可以操作
+    pub async fn transfer_group_ownership(
+        &self,
+        group_code: i64,
+        new_owner_uin: i64,
+    ) -> RQResult<()> {
+        let uin = self.uin();
+        let group = self
+            .find_group(group_code, true)
+            .await
+            .ok_or_else(|| RQError::Other("group not found".into()))?;
+        if group.info.read().await.owner_uin != uin {
+            return Err(RQError::PermissionDenied);
+        }
+        let req =
+            self.engine
+                .read()
+                .await
+                .build_group_transfer_packet(group_code, uin, new_owner_uin);
+        let resp = self.send_and_wait(req).await?;
+        self.engine
+            .read()
+            .await
+            .decode_group_operation_response(resp.body)?;
+        self.modify_cached_group_info(group_code, |info| info.owner_uin = new_owner_uin)
+            .await;
+        Ok(())
+    }
+
     /// 全员禁言
     pub async fn group_mute_all(&self, group_code: i64, mute: bool) -> RQResult<()> {
         let req = self
@@ -367,6 +640,82 @@ impl super::super::Client {
         Ok(())
     }
 
+    /// 获取群简介
+    pub async fn get_group_notice(&self, group_code: i64) -> RQResult<String> {
+        self.get_group_info(group_code)
+            .await?
+            .map(|info| info.notice)
+            .ok_or_else(|| RQError::Other("failed to get group".into()))
+    }
+
+    /// 获取入群问题，没有设置时返回 None
+    pub async fn get_group_entrance_question(
+        &self,
+        group_code: i64,
+    ) -> RQResult<Option<EntranceQuestion>> {
+        let req = self
+            .engine
+            .read()
+            .await
+            .build_group_entrance_question_request_packet(group_code);
+        let resp = self.send_and_wait(req).await?;
+        self.engine
+            .read()
+            .await
+            .decode_group_entrance_question_response(resp.body)
+    }
+
+    /// 设置入群问题，answer 为空时由管理员审核
+    pub async fn set_group_entrance_question(
+        &self,
+        group_code: i64,
+        question: &str,
+        answer: &str,
+    ) -> RQResult<()> {
+        let req = self
+            .engine
+            .read()
+            .await
+            .build_group_entrance_question_update_packet(
+                group_code,
+                question.to_string(),
+                answer.to_string(),
+            );
+        let resp = self.send_and_wait(req).await?;
+        self.engine
+            .read()
+            .await
+            .decode_group_operation_response(resp.body)
+    }
+
+    /// 修改群简介
+    pub async fn set_group_notice(&self, group_code: i64, notice: &str) -> RQResult<()> {
+        let req = self
+            .engine
+            .read()
+            .await
+            .build_group_notice_update_packet(group_code, notice.to_string());
+        let resp = self.send_and_wait(req).await?;
+        self.engine
+            .read()
+            .await
+            .decode_group_operation_response(resp.body)?;
+        self.modify_cached_group_info(group_code, |info| info.notice = notice.to_string())
+            .await;
+        Ok(())
+    }
+
+    // 原地修改缓存中的群信息，群不在缓存中时忽略
+    pub(crate) async fn modify_cached_group_info<F>(&self, group_code: i64, f: F)
+    where
+        F: FnOnce(&mut GroupInfo),
+    {
+        let group = self.groups.read().await.get(&group_code).cloned();
+        if let Some(group) = group {
+            f(&mut *group.info.write().await);
+        }
+    }
+
     /// 设置群管理员
     ///
     /// flag: true 设置管理员 false 取消管理员
@@ -425,7 +774,7 @@ impl super::super::Client {
         Ok(())
     }
 
-    /// 获取群 @全体成员 剩余次数
+    /// 获取群 @全体成员 剩余次数，发送 @全体 前可以用 is_available 检查
     pub async fn group_at_all_remain(&self, group_code: i64) -> RQResult<GroupAtAllRemainInfo> {
         let req = self
             .engine
@@ -655,7 +1004,7 @@ impl super::super::Client {
             .decode_group_try_up_ptt_resp(resp)?;
         Ok(GroupAudio(pb::msg::Ptt {
             file_type: Some(4),
-            src_uin: Some(self.uin().await),
+            src_uin: Some(self.uin()),
             file_name: Some(format!("{}.amr", encode_hex(&md5))),
             file_md5: Some(md5),
             file_size: Some(size as i32),
@@ -678,6 +1027,69 @@ impl super::super::Client {
         self.engine.read().await.decode_group_ptt_down(resp.body)
     }
 
+    // 群文件相关接口调用前检查协议是否支持，避免发送后收到含义不明的错误码
+    async fn check_group_files_supported(&self) -> RQResult<()> {
+        let protocol = self.engine.read().await.transport.version.protocol.clone();
+        if !protocol.capabilities().has_group_files {
+            return Err(RQError::UnsupportedOnProtocol(protocol));
+        }
+        Ok(())
+    }
+
+    /// 获取群文件列表，folder_id 为 "/" 时是根目录，is_end 为 false 时用 next_index 继续获取
+    pub async fn get_group_files(
+        &self,
+        group_code: i64,
+        folder_id: &str,
+        start_index: u32,
+    ) -> RQResult<GroupFileList> {
+        self.check_group_files_supported().await?;
+        let req = self
+            .engine
+            .read()
+            .await
+            .build_group_file_list_request_packet(group_code, folder_id.to_string(), start_index);
+        let resp = self.send_and_wait(req).await?;
+        self.engine
+            .read()
+            .await
+            .decode_group_file_list_response(group_code, resp.body)
+    }
+
+    /// 获取群文件数量
+    pub async fn get_group_file_count(&self, group_code: i64) -> RQResult<GroupFileCount> {
+        self.check_group_files_supported().await?;
+        let req = self
+            .engine
+            .read()
+            .await
+            .build_group_file_count_request_packet(group_code);
+        let resp = self.send_and_wait(req).await?;
+        self.engine
+            .read()
+            .await
+            .decode_group_file_count_response(resp.body)
+    }
+
+    /// 获取群文件下载链接
+    pub async fn get_group_file_url(&self, file: &GroupFile) -> RQResult<String> {
+        self.check_group_files_supported().await?;
+        let req = self
+            .engine
+            .read()
+            .await
+            .build_group_file_download_request_packet(
+                file.group_code,
+                file.file_id.clone(),
+                file.bus_id,
+            );
+        let resp = self.send_and_wait(req).await?;
+        self.engine
+            .read()
+            .await
+            .decode_group_file_download_response(resp.body)
+    }
+
     // 群精华消息操作
     // flag true 设置群精华消息 ｜ false 移除群精华消息
     pub async fn group_essence_operation(
@@ -701,4 +1113,196 @@ impl super::super::Client {
             .decode_essence_msg_response(resp.body)?;
         Ok(decode)
     }
+
+    /// 设置群精华消息
+    pub async fn set_essence_message(
+        &self,
+        group_code: i64,
+        msg_seq: i32,
+        msg_rand: i32,
+    ) -> RQResult<()> {
+        let resp = self
+            .group_essence_operation(group_code, msg_seq, msg_rand, true)
+            .await?;
+        check_essence_response(resp)
+    }
+
+    /// 移除群精华消息
+    pub async fn remove_essence_message(
+        &self,
+        group_code: i64,
+        msg_seq: i32,
+        msg_rand: i32,
+    ) -> RQResult<()> {
+        let resp = self
+            .group_essence_operation(group_code, msg_seq, msg_rand, false)
+            .await?;
+        check_essence_response(resp)
+    }
+
+    /// 获取群精华消息列表，最多拉取 50 页
+    pub async fn get_essence_message_list(&self, group_code: i64) -> RQResult<Vec<EssenceMessage>> {
+        const ESSENCE_MAX_PAGES: u32 = 50;
+        let bkn = self.bkn().await;
+        let mut list = Vec::new();
+        for page in 0..ESSENCE_MAX_PAGES {
+            let url = format!("https://qun.qq.com/cgi-bin/group_digest/digest_list?bkn={bkn}&group_code={group_code}&page_start={page}&page_limit=20");
+            let rsp: DigestListResponse = self.web_get("qun.qq.com", &url, &[]).await?;
+            if rsp.retcode != 0 {
+                return Err(RQError::Other(rsp.retmsg));
+            }
+            let data = rsp.data.unwrap_or_default();
+            if data.msg_list.is_empty() {
+                break;
+            }
+            list.extend(data.msg_list.into_iter().map(|m| EssenceMessage {
+                group_code,
+                msg_seq: m.msg_seq,
+                msg_rand: m.msg_random,
+                sender_uin: m.sender_uin.parse().unwrap_or_default(),
+                sender_nick: m.sender_nick,
+                sender_time: m.sender_time,
+                add_digest_uin: m.add_digest_uin.parse().unwrap_or_default(),
+                add_digest_nick: m.add_digest_nick,
+                add_digest_time: m.add_digest_time,
+            }));
+            if data.is_end {
+                break;
+            }
+        }
+        Ok(list)
+    }
+
+    /// 发起群投票，返回投票 ID
+    pub async fn create_group_poll(
+        &self,
+        group_code: i64,
+        question: &str,
+        options: Vec<String>,
+        is_anonymous: bool,
+        is_multi_select: bool,
+    ) -> RQResult<String> {
+        if options.len() < 2 {
+            return Err(RQError::InvalidArgument(
+                "poll needs at least 2 options".into(),
+            ));
+        }
+        let bkn = self.bkn().await;
+        let mut form = vec![
+            ("bkn", bkn.to_string()),
+            ("qid", group_code.to_string()),
+            ("title", question.to_string()),
+            ("anonymous", (is_anonymous as u8).to_string()),
+            ("multi", (is_multi_select as u8).to_string()),
+        ];
+        form.extend(options.into_iter().map(|o| ("options[]", o)));
+        let rsp: PollCreateResponse = self
+            .web_post_form(
+                "qun.qq.com",
+                "https://qun.qq.com/cgi-bin/group_vote/create_vote",
+                &form,
+            )
+            .await?;
+        if rsp.ec != 0 {
+            return Err(RQError::Other(rsp.em));
+        }
+        Ok(rsp.vote_id)
+    }
+
+    /// 获取群投票结果
+    pub async fn get_group_poll_result(
+        &self,
+        group_code: i64,
+        poll_id: &str,
+    ) -> RQResult<PollResult> {
+        let bkn = self.bkn().await;
+        let query = [
+            ("bkn", bkn.to_string()),
+            ("qid", group_code.to_string()),
+            ("vote_id", poll_id.to_string()),
+        ];
+        let rsp: PollDetailResponse = self
+            .web_get(
+                "qun.qq.com",
+                "https://qun.qq.com/cgi-bin/group_vote/get_vote_detail",
+                &query,
+            )
+            .await?;
+        if rsp.ec != 0 {
+            return Err(RQError::Other(rsp.em));
+        }
+        Ok(PollResult {
+            poll_id: poll_id.to_string(),
+            question: rsp.title,
+            options: rsp
+                .options
+                .into_iter()
+                .map(|o| PollOption {
+                    text: o.text,
+                    votes: o.count,
+                })
+                .collect(),
+            voter_count: rsp.voter_count,
+        })
+    }
+}
+
+fn check_essence_response(resp: pb::oidb::EacRspBody) -> RQResult<()> {
+    match resp.error_code.unwrap_or_default() {
+        0 => Ok(()),
+        _ => Err(RQError::Other(resp.wording.unwrap_or_default())),
+    }
+}
+
+#[derive(serde::Deserialize, Default)]
+#[serde(default)]
+struct DigestListResponse {
+    retcode: i32,
+    retmsg: String,
+    data: Option<DigestListData>,
+}
+
+#[derive(serde::Deserialize, Default)]
+#[serde(default)]
+struct DigestListData {
+    msg_list: Vec<DigestMessage>,
+    is_end: bool,
+}
+
+#[derive(serde::Deserialize, Default)]
+#[serde(default)]
+struct DigestMessage {
+    msg_seq: i32,
+    msg_random: i32,
+    sender_uin: String,
+    sender_nick: String,
+    sender_time: i64,
+    add_digest_uin: String,
+    add_digest_nick: String,
+    add_digest_time: i64,
+}
+
+#[derive(serde::Deserialize, Default)]
+#[serde(default)]
+struct PollCreateResponse {
+    ec: i32,
+    em: String,
+    vote_id: String,
+}
+
+#[derive(serde::Deserialize, Default)]
+#[serde(default)]
+struct PollDetailResponse {
+    ec: i32,
+    em: String,
+    title: String,
+    options: Vec<PollDetailOption>,
+    voter_count: u32,
+}
+
+#[derive(serde::Deserialize, Default)]
+#[serde(default)]
+struct PollDetailOption {
+    text: String,
+    count: u32,
 }
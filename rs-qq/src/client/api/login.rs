@@ -1,6 +1,8 @@
 use std::sync::atomic::Ordering;
+use std::sync::Arc;
 
 use crate::engine::command::wtlogin::*;
+use crate::engine::protocol::packet::Packet;
 use crate::engine::token::Token;
 use crate::jce::SvcRespRegister;
 use crate::{RQError, RQResult};
@@ -9,6 +11,10 @@ use crate::{RQError, RQResult};
 impl super::super::Client {
     /// 二维码登录 - 获取二维码
     pub async fn fetch_qrcode(&self) -> RQResult<QRCodeState> {
+        let protocol = self.engine.read().await.transport.version.protocol.clone();
+        if !protocol.capabilities().has_qrcode_login {
+            return Err(RQError::UnsupportedOnProtocol(protocol));
+        }
         let req = self.engine.read().await.build_qrcode_fetch_request_packet();
         let resp = self.send_and_wait(req).await?;
         let resp = self
@@ -39,7 +45,7 @@ impl super::super::Client {
 
     /// 二维码登录 - 登录 ( 可能还需要 device_lock_login )
     pub async fn qrcode_login(
-        &self,
+        self: &Arc<Self>,
         tmp_pwd: &[u8],
         tmp_no_pic_sig: &[u8],
         tgt_qr: &[u8],
@@ -57,7 +63,7 @@ impl super::super::Client {
 
     /// 密码登录 - 提交密码md5
     pub async fn password_md5_login(
-        &self,
+        self: &Arc<Self>,
         uin: i64,
         password_md5: &[u8],
     ) -> RQResult<LoginResponse> {
@@ -73,13 +79,17 @@ impl super::super::Client {
         Ok(resp)
     }
 
-    pub async fn password_login(&self, uin: i64, password: &str) -> RQResult<LoginResponse> {
+    pub async fn password_login(
+        self: &Arc<Self>,
+        uin: i64,
+        password: &str,
+    ) -> RQResult<LoginResponse> {
         self.password_md5_login(uin, &md5::compute(password).to_vec())
             .await
     }
 
     /// 密码登录 - 请求短信验证码
-    pub async fn request_sms(&self) -> RQResult<LoginResponse> {
+    pub async fn request_sms(self: &Arc<Self>) -> RQResult<LoginResponse> {
         let req = self.engine.read().await.build_sms_request_packet();
         let resp = self.send_and_wait(req).await?;
         let resp = self.engine.read().await.decode_login_response(resp.body)?;
@@ -88,7 +98,7 @@ impl super::super::Client {
     }
 
     /// 密码登录 - 提交短信验证码
-    pub async fn submit_sms_code(&self, code: &str) -> RQResult<LoginResponse> {
+    pub async fn submit_sms_code(self: &Arc<Self>, code: &str) -> RQResult<LoginResponse> {
         let req = self
             .engine
             .read()
@@ -101,7 +111,7 @@ impl super::super::Client {
     }
 
     /// 密码登录 - 提交滑块ticket
-    pub async fn submit_ticket(&self, ticket: &str) -> RQResult<LoginResponse> {
+    pub async fn submit_ticket(self: &Arc<Self>, ticket: &str) -> RQResult<LoginResponse> {
         let req = self.engine.read().await.build_ticket_submit_packet(ticket);
         let resp = self.send_and_wait(req).await?;
         let resp = self.engine.read().await.decode_login_response(resp.body)?;
@@ -110,7 +120,7 @@ impl super::super::Client {
     }
 
     /// 设备锁登录 - 二维码、密码登录都需要
-    pub async fn device_lock_login(&self) -> RQResult<LoginResponse> {
+    pub async fn device_lock_login(self: &Arc<Self>) -> RQResult<LoginResponse> {
         let req = self.engine.read().await.build_device_lock_login_packet();
         let resp = self.send_and_wait(req).await?;
         let resp = self.engine.read().await.decode_login_response(resp.body)?;
@@ -119,13 +129,13 @@ impl super::super::Client {
     }
 
     /// token 登录
-    pub async fn token_login(&self, token: Token) -> RQResult<LoginResponse> {
+    pub async fn token_login(self: &Arc<Self>, token: Token) -> RQResult<LoginResponse> {
         self.load_token(token).await;
         self.request_change_sig(None).await
     }
 
     pub(crate) async fn request_change_sig(
-        &self,
+        self: &Arc<Self>,
         main_sig_map: Option<u32>,
     ) -> RQResult<LoginResponse> {
         let req = self
@@ -143,9 +153,15 @@ impl super::super::Client {
         Ok(resp)
     }
 
-    /// 注册客户端，登录后必须注册
+    /// 注册客户端，登录后必须注册，心跳每 7 次也会重新注册
+    /// 恢复 token 后可以手动调用，不必要的频繁注册可能触发新设备安全验证
     pub async fn register_client(&self) -> RQResult<SvcRespRegister> {
         let req = self.engine.read().await.build_client_register_packet();
+        self.send_register_packet(req).await
+    }
+
+    // 发送已经构建好的 StatSvc.register 包
+    pub(crate) async fn send_register_packet(&self, req: Packet) -> RQResult<SvcRespRegister> {
         let resp = self.send_and_wait(req).await?;
         let resp = self
             .engine
@@ -1,17 +1,26 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
 use bytes::Bytes;
-use std::sync::atomic::Ordering;
+use futures::{stream, Stream, StreamExt, TryStreamExt};
 
 use crate::engine::command::message_svc::MessageSyncResponse;
 use crate::engine::command::oidb_svc::*;
+use crate::engine::hex::encode_hex;
+use crate::engine::msg::elem::{FriendImage, GroupImage};
 use crate::engine::pb;
 use crate::engine::structs::Status;
 use crate::engine::structs::SummaryCardInfo;
+use crate::engine::structs::{GroupMessage, PrivateMessage};
 use crate::jce::SvcDevLoginInfo;
+use crate::structs::DeviceListResponse;
 use crate::{RQError, RQResult};
 
 mod friend;
 mod group;
 mod login;
+mod qzone;
+mod web;
 
 /// API
 impl super::Client {
@@ -35,8 +44,13 @@ impl super::Client {
         Ok(())
     }
 
-    /// 修改签名
+    /// 修改签名，最长 254 字节
     pub async fn update_signature(&self, signature: String) -> RQResult<()> {
+        if signature.len() > 254 {
+            return Err(RQError::InvalidArgument(
+                "signature must not exceed 254 bytes".into(),
+            ));
+        }
         let req = self
             .engine
             .read()
@@ -46,8 +60,19 @@ impl super::Client {
         Ok(())
     }
 
+    /// 清空签名
+    pub async fn clear_signature(&self) -> RQResult<()> {
+        self.update_signature(String::new()).await
+    }
+
+    /// 获取自己的签名
+    pub async fn get_self_signature(&self) -> RQResult<String> {
+        Ok(self.get_summary_info(self.uin()).await?.sign)
+    }
+
     /// 修改个人资料
     pub async fn update_profile_detail(&self, profile: ProfileDetailUpdate) -> RQResult<()> {
+        profile.validate()?;
         let req = self
             .engine
             .read()
@@ -63,40 +88,99 @@ impl super::Client {
             .engine
             .read()
             .await
-            .build_get_offline_msg_request_packet(self.last_message_time.load(Ordering::SeqCst));
+            .build_get_offline_msg_request_packet(self.last_message_time());
         let _resp = self.send_and_wait(req).await?;
         Ok(())
     }
 
-    /// 获取通过安全验证的设备
-    pub async fn get_allowed_clients(&self) -> RQResult<Vec<SvcDevLoginInfo>> {
+    /// 获取通过安全验证的设备，current_session_info 是当前登录的设备
+    pub async fn get_allowed_clients(&self) -> RQResult<DeviceListResponse> {
         let req = self.engine.read().await.build_device_list_request_packet();
         let resp = self.send_and_wait(req).await?;
-        self.engine.read().await.decode_dev_list_response(resp.body)
+        let engine = self.engine.read().await;
+        let devices = engine.decode_dev_list_response(resp.body)?;
+        let transport = &engine.transport;
+        // 列表中没有当前设备时用本地信息填充
+        let current_session_info = devices
+            .iter()
+            .find(|d| d.guid == transport.sig.guid)
+            .cloned()
+            .unwrap_or_else(|| SvcDevLoginInfo {
+                app_id: transport.version.app_id as i64,
+                guid: transport.sig.guid.clone(),
+                device_name: transport.device.model.clone(),
+                device_type_info: transport.device.brand.clone(),
+                ..Default::default()
+            });
+        Ok(DeviceListResponse {
+            devices,
+            current_session_info: Some(current_session_info),
+        })
     }
 
-    /// 文本翻译
+    /// 文本翻译，重复的文本只翻译一次，超过单次上限自动分批
     pub async fn translate(
         &self,
         src_language: String,
         dst_language: String,
         src_text_list: Vec<String>,
     ) -> RQResult<Vec<String>> {
-        let req = self.engine.read().await.build_translate_request_packet(
-            src_language,
-            dst_language,
-            src_text_list.clone(),
-        );
-        let resp = self.send_and_wait(req).await?;
-        let translations = self
-            .engine
-            .read()
+        // 单次翻译请求最多 10 条
+        const TRANSLATE_BATCH_SIZE: usize = 10;
+        let (unique, index) = dedup_texts(&src_text_list);
+        let mut translations = Vec::with_capacity(unique.len());
+        for chunk in unique.chunks(TRANSLATE_BATCH_SIZE) {
+            let translated = self
+                .translate_with_retry(&src_language, &dst_language, chunk.to_vec())
+                .await?;
+            if translated.len() != chunk.len() {
+                return Err(RQError::Other("translate length error".into()));
+            }
+            translations.extend(translated);
+        }
+        Ok(index.into_iter().map(|i| translations[i].clone()).collect())
+    }
+
+    /// 按 Language 翻译，超过单次上限（10 条）自动分批
+    pub async fn translate_chunked(
+        &self,
+        src: Language,
+        dst: Language,
+        texts: Vec<String>,
+    ) -> RQResult<Vec<String>> {
+        self.translate(src.code().into(), dst.code().into(), texts)
             .await
-            .decode_translate_response(resp.body)?;
-        if translations.len() != src_text_list.len() {
-            return Err(RQError::Other("translate length error".into()));
+    }
+
+    // 服务端限流时按 1s, 2s, 4s... 重试，最多 translate_max_retries 次
+    async fn translate_with_retry(
+        &self,
+        src_language: &str,
+        dst_language: &str,
+        src_text_list: Vec<String>,
+    ) -> RQResult<Vec<String>> {
+        let mut retries = 0;
+        loop {
+            let req = self.engine.read().await.build_translate_request_packet(
+                src_language.into(),
+                dst_language.into(),
+                src_text_list.clone(),
+            );
+            let resp = self.send_and_wait(req).await?;
+            match self
+                .engine
+                .read()
+                .await
+                .decode_translate_response(resp.body)
+            {
+                Err(RQError::RateLimited(msg)) if retries < self.translate_max_retries => {
+                    tracing::warn!(target: "rs_qq", "translate rate limited: {}, retrying", msg);
+                    tokio::time::sleep(Duration::from_secs(1 << retries)).await;
+                    retries += 1;
+                }
+                result => return result,
+            }
         }
-        Ok(translations)
     }
 
     pub async fn send_like(&self, uin: i64, count: i32) -> RQResult<()> {
@@ -105,21 +189,34 @@ impl super::Client {
         Ok(())
     }
 
-    // TODO 待完善
     // 图片 OCR
+    #[deprecated = "use image_ocr_v2"]
     pub async fn image_ocr(
         &self,
         img_url: String,
         md5: String,
         size: i32,
-        wight: i32,
+        width: i32,
+        height: i32,
+    ) -> RQResult<OcrResponse> {
+        self.image_ocr_v2(img_url, md5, size, width, height).await
+    }
+
+    // TODO 待完善
+    // 图片 OCR
+    pub async fn image_ocr_v2(
+        &self,
+        img_url: String,
+        md5: String,
+        size: i32,
+        width: i32,
         height: i32,
     ) -> RQResult<OcrResponse> {
         let req = self
             .engine
             .read()
             .await
-            .build_image_ocr_request_packet(img_url, md5, size, wight, height);
+            .build_image_ocr_request_packet(img_url, md5, size, width, height);
         let resp = self.send_and_wait(req).await?;
 
         let decode = self
@@ -130,6 +227,30 @@ impl super::Client {
         Ok(decode)
     }
 
+    // 群图片 OCR
+    pub async fn image_ocr_from_image(&self, image: &GroupImage) -> RQResult<OcrResponse> {
+        self.image_ocr_v2(
+            image.url(),
+            encode_hex(&image.md5).to_uppercase(),
+            image.size,
+            image.width,
+            image.height,
+        )
+        .await
+    }
+
+    // 好友图片 OCR，好友图片没有宽高信息
+    pub async fn image_ocr_from_friend_image(&self, image: &FriendImage) -> RQResult<OcrResponse> {
+        self.image_ocr_v2(
+            image.url(),
+            encode_hex(&image.md5).to_uppercase(),
+            image.size,
+            0,
+            0,
+        )
+        .await
+    }
+
     // 标记消息已收到，server 不再重复推送
     pub async fn delete_message(&self, items: Vec<pb::MessageItem>) -> RQResult<()> {
         let req = self
@@ -141,6 +262,35 @@ impl super::Client {
         Ok(())
     }
 
+    /// 标记群消息已读
+    pub async fn ack_message(&self, msg: &GroupMessage) -> RQResult<()> {
+        let seq = msg
+            .seqs
+            .iter()
+            .max()
+            .cloned()
+            .ok_or_else(|| RQError::Other("group message seqs is empty".into()))?;
+        self.mark_group_message_readed(msg.group_code, seq).await
+    }
+
+    /// 标记私聊消息已收到
+    pub async fn ack_friend_message(&self, msg: &PrivateMessage) -> RQResult<()> {
+        let items = msg
+            .seqs
+            .iter()
+            .zip(msg.rands.iter())
+            .map(|(seq, rand)| pb::MessageItem {
+                from_uin: msg.from_uin,
+                to_uin: msg.target,
+                msg_type: 166,
+                msg_seq: *seq,
+                msg_uid: (1 << 56) | *rand as u32 as i64,
+                ..Default::default()
+            })
+            .collect();
+        self.delete_message(items).await
+    }
+
     // sync message
     async fn sync_message(&self, sync_flag: i32) -> RQResult<MessageSyncResponse> {
         let time = chrono::Utc::now().timestamp();
@@ -156,44 +306,40 @@ impl super::Client {
             .decode_message_svc_packet(resp.body)
     }
 
-    // 从服务端拉取通知
-    pub(crate) async fn sync_all_message(&self) -> RQResult<Vec<pb::msg::Message>> {
+    /// 从服务端拉取通知，since 不为空时忽略早于该时间（秒）的消息
+    ///
+    /// 每拉取一页就返回一页，不会把所有消息堆在内存中
+    pub fn sync_all_message(
+        &self,
+        since: Option<i64>,
+    ) -> impl Stream<Item = RQResult<pb::msg::Message>> + '_ {
         const SYNC_START: i32 = 0;
         const _SYNC_CONTINUE: i32 = 1;
         const SYNC_STOP: i32 = 2;
+        // 每次 delete_message 最多标记的消息数量
+        const DELETE_BATCH_SIZE: usize = 100;
 
-        let mut sync_flag = SYNC_START;
-        let mut msgs = Vec::new();
-        loop {
-            let resp = match self.sync_message(sync_flag).await {
-                Ok(resp) => resp,
-                Err(_) => {
-                    tracing::warn!(target: "rs_qq", "failed to sync_message");
-                    break;
-                }
+        stream::try_unfold(Some(SYNC_START), move |sync_flag| async move {
+            let sync_flag = match sync_flag {
+                Some(sync_flag) => sync_flag,
+                None => return Ok(None),
             };
-            if let Err(err) = self
-                .delete_message(
-                    resp.msgs
-                        .iter()
-                        .map(|m| {
-                            let head = m.head.as_ref().unwrap();
-                            pb::MessageItem {
-                                from_uin: head.from_uin(),
-                                to_uin: head.to_uin(),
-                                msg_type: head.msg_type(),
-                                msg_seq: head.msg_seq(),
-                                msg_uid: head.msg_uid(),
-                                ..Default::default()
-                            }
-                        })
-                        .collect(),
-                )
-                .await
-            {
-                tracing::warn!(target: "rs_qq", "failed to delete_message: {}",err);
-                break;
-            }
+            let resp = self.sync_message(sync_flag).await?;
+            let items: Vec<pb::MessageItem> = resp
+                .msgs
+                .iter()
+                .filter_map(|m| {
+                    let head = m.head.as_ref()?;
+                    Some(pb::MessageItem {
+                        from_uin: head.from_uin(),
+                        to_uin: head.to_uin(),
+                        msg_type: head.msg_type(),
+                        msg_seq: head.msg_seq(),
+                        msg_uid: head.msg_uid(),
+                        ..Default::default()
+                    })
+                })
+                .collect();
             match resp.msg_rsp_type {
                 0 => {
                     let mut engine = self.engine.write().await;
@@ -218,13 +364,41 @@ impl super::Client {
                 }
                 _ => {}
             }
-            msgs.extend(resp.msgs);
-            sync_flag = resp.sync_flag;
-            if sync_flag == SYNC_STOP {
-                break;
+            // 每页都更新游标，中途退出时下次从这里继续
+            self.set_last_message_time(
+                resp.msgs
+                    .last()
+                    .and_then(|m| m.head.as_ref())
+                    .map(|head| head.msg_time() as i64)
+                    .unwrap_or_else(|| chrono::Utc::now().timestamp()),
+            );
+            for chunk in items.chunks(DELETE_BATCH_SIZE) {
+                if let Err(err) = self.delete_message(chunk.to_vec()).await {
+                    tracing::warn!(target: "rs_qq", "failed to delete_message: {}", err);
+                    break;
+                }
             }
-        }
-        Ok(msgs)
+            let msgs: Vec<pb::msg::Message> = resp
+                .msgs
+                .into_iter()
+                .filter(|m| match since {
+                    Some(since) => m
+                        .head
+                        .as_ref()
+                        .map(|head| head.msg_time() as i64 >= since)
+                        .unwrap_or_default(),
+                    None => true,
+                })
+                .collect();
+            let next_sync_flag = if resp.sync_flag == SYNC_STOP {
+                None
+            } else {
+                Some(resp.sync_flag)
+            };
+            Ok(Some((msgs, next_sync_flag)))
+        })
+        .map_ok(|msgs| stream::iter(msgs.into_iter().map(Ok)))
+        .try_flatten()
     }
 
     // 获取名片信息
@@ -238,6 +412,58 @@ impl super::Client {
         self.engine
             .read()
             .await
-            .decode_summary_card_response(resp.body)
+            .decode_summary_card_response(uin, resp.body)
+    }
+
+    // 批量获取名片信息，最多同时 5 个请求，失败的 uin 不会出现在结果中
+    pub async fn get_summary_info_batch(
+        &self,
+        uins: &[i64],
+    ) -> RQResult<HashMap<i64, SummaryCardInfo>> {
+        Ok(stream::iter(uins.iter().copied())
+            .map(|uin| async move { (uin, self.get_summary_info(uin).await) })
+            .buffer_unordered(5)
+            .filter_map(|(uin, result)| async move {
+                match result {
+                    Ok(info) => Some((uin, info)),
+                    Err(err) => {
+                        tracing::warn!(target: "rs_qq", "failed to get summary info of {}: {}", uin, err);
+                        None
+                    }
+                }
+            })
+            .collect()
+            .await)
+    }
+}
+
+// 翻译文本去重，返回 (去重后的文本, 原文本在去重结果中的下标)
+fn dedup_texts(texts: &[String]) -> (Vec<String>, Vec<usize>) {
+    let mut unique: Vec<String> = Vec::new();
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+    let index = texts
+        .iter()
+        .map(|t| {
+            *positions.entry(t.as_str()).or_insert_with(|| {
+                unique.push(t.clone());
+                unique.len() - 1
+            })
+        })
+        .collect();
+    (unique, index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dedup_texts() {
+        let texts: Vec<String> = vec!["hello".into(), "world".into(), "hello".into()];
+        let (unique, index) = dedup_texts(&texts);
+        assert_eq!(unique, vec!["hello".to_string(), "world".to_string()]);
+        assert_eq!(index, vec![0, 1, 0]);
+        let restored: Vec<&String> = index.iter().map(|i| &unique[*i]).collect();
+        assert_eq!(restored, texts.iter().collect::<Vec<_>>());
     }
 }
@@ -0,0 +1,81 @@
+use crate::structs::QzonePost;
+use crate::{RQError, RQResult};
+
+/// QQ 空间
+impl crate::Client {
+    /// 点赞说说
+    pub async fn like_qzone_post(&self, owner_uin: i64, tid: &str) -> RQResult<()> {
+        let uin = self.uin();
+        let gtk = self.gtk("qzone.qq.com").await;
+        let url = format!("https://user.qzone.qq.com/proxy/domain/w.qzone.qq.com/cgi-bin/likes/internal_dolike_app?g_tk={gtk}");
+        let key = format!("http://user.qzone.qq.com/{owner_uin}/mood/{tid}");
+        let form = [
+            ("opuin", uin.to_string()),
+            ("unikey", key.clone()),
+            ("curkey", key),
+            ("appid", "311".to_string()),
+            ("typeid", "0".to_string()),
+            ("fid", tid.to_string()),
+            ("from", "1".to_string()),
+            ("active", "0".to_string()),
+            ("fupdate", "1".to_string()),
+            ("abstime", chrono::Utc::now().timestamp().to_string()),
+            ("format", "json".to_string()),
+        ];
+        let rsp: QzoneResponse = self.web_post_form("qzone.qq.com", &url, &form).await?;
+        if rsp.code != 0 {
+            return Err(RQError::Other(rsp.message));
+        }
+        Ok(())
+    }
+
+    /// 获取某人的说说列表（最近 20 条）
+    pub async fn get_qzone_feed(&self, uin: i64) -> RQResult<Vec<QzonePost>> {
+        let gtk = self.gtk("qzone.qq.com").await;
+        let url = format!("https://user.qzone.qq.com/proxy/domain/taotao.qq.com/cgi-bin/emotion_cgi_msglist_v6?uin={uin}&pos=0&num=20&format=json&need_private_comment=1&g_tk={gtk}");
+        let rsp: QzoneFeedResponse = self.web_get("qzone.qq.com", &url, &[]).await?;
+        if rsp.code != 0 {
+            return Err(RQError::Other(rsp.message));
+        }
+        Ok(rsp
+            .msglist
+            .unwrap_or_default()
+            .into_iter()
+            .map(|m| QzonePost {
+                tid: m.tid,
+                owner_uin: m.uin,
+                owner_name: m.name,
+                content: m.content,
+                created_time: m.created_time,
+                comment_count: m.cmtnum,
+            })
+            .collect())
+    }
+}
+
+#[derive(serde::Deserialize, Default)]
+#[serde(default)]
+struct QzoneResponse {
+    code: i32,
+    message: String,
+}
+
+#[derive(serde::Deserialize, Default)]
+#[serde(default)]
+struct QzoneFeedResponse {
+    code: i32,
+    message: String,
+    // 没有说说时为 null
+    msglist: Option<Vec<QzoneFeedItem>>,
+}
+
+#[derive(serde::Deserialize, Default)]
+#[serde(default)]
+struct QzoneFeedItem {
+    tid: String,
+    uin: i64,
+    name: String,
+    content: String,
+    created_time: i64,
+    cmtnum: u32,
+}
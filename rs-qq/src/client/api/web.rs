@@ -0,0 +1,91 @@
+use cached::Cached;
+use serde::de::DeserializeOwned;
+
+use crate::{RQError, RQResult};
+
+// g_tk / bkn 算法
+fn hash_gtk(key: &[u8]) -> i64 {
+    let mut hash: i64 = 5381;
+    for b in key {
+        hash = hash.wrapping_add((hash << 5).wrapping_add(*b as i64));
+    }
+    hash & 0x7fffffff
+}
+
+/// 网页接口（qun.qq.com 等）
+impl crate::Client {
+    /// 计算网页接口需要的 bkn (g_tk)
+    pub async fn bkn(&self) -> i64 {
+        hash_gtk(&self.engine.read().await.transport.sig.s_key)
+    }
+
+    /// 计算某个域名的 g_tk，有 p_skey 时使用 p_skey，结果缓存 5 分钟
+    pub async fn gtk(&self, domain: &str) -> i64 {
+        if let Some(gtk) = self.gtk_cache.write().await.cache_get(&domain.to_string()) {
+            return *gtk;
+        }
+        let gtk = {
+            let engine = self.engine.read().await;
+            match engine.transport.sig.ps_key_map.get(domain) {
+                Some(ps_key) => hash_gtk(ps_key),
+                None => hash_gtk(&engine.transport.sig.s_key),
+            }
+        };
+        self.gtk_cache
+            .write()
+            .await
+            .cache_set(domain.to_string(), gtk);
+        gtk
+    }
+
+    // 获取某个域名的网页 cookie
+    pub(crate) async fn web_cookies(&self, domain: &str) -> String {
+        let uin = self.uin();
+        let engine = self.engine.read().await;
+        let s_key = String::from_utf8_lossy(&engine.transport.sig.s_key).to_string();
+        let mut cookies = format!("uin=o{uin}; skey={s_key};");
+        if let Some(ps_key) = engine.transport.sig.ps_key_map.get(domain) {
+            cookies += &format!(" p_uin=o{uin}; p_skey={};", String::from_utf8_lossy(ps_key));
+        }
+        cookies
+    }
+
+    // query 中的参数会被 URL 编码后追加到 url
+    pub(crate) async fn web_get<T: DeserializeOwned>(
+        &self,
+        domain: &str,
+        url: &str,
+        query: &[(&str, String)],
+    ) -> RQResult<T> {
+        let cookies = self.web_cookies(domain).await;
+        reqwest::Client::new()
+            .get(url)
+            .query(query)
+            .header(reqwest::header::COOKIE, cookies)
+            .send()
+            .await
+            .map_err(|e| RQError::Other(format!("web request error, {e}")))?
+            .json::<T>()
+            .await
+            .map_err(|e| RQError::Decode(format!("web response decode error, {e}")))
+    }
+
+    pub(crate) async fn web_post_form<T: DeserializeOwned>(
+        &self,
+        domain: &str,
+        url: &str,
+        form: &[(&str, String)],
+    ) -> RQResult<T> {
+        let cookies = self.web_cookies(domain).await;
+        reqwest::Client::new()
+            .post(url)
+            .header(reqwest::header::COOKIE, cookies)
+            .form(form)
+            .send()
+            .await
+            .map_err(|e| RQError::Other(format!("web request error, {e}")))?
+            .json::<T>()
+            .await
+            .map_err(|e| RQError::Decode(format!("web response decode error, {e}")))
+    }
+}
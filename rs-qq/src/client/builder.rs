@@ -0,0 +1,122 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use crate::client::handler::Handler;
+use crate::engine::protocol::device::Device;
+use crate::engine::protocol::version::{get_version, Protocol};
+use crate::{Client, RQError, RQResult};
+
+/// SOCKS5 代理服务器，设置 username 时使用用户名密码认证
+#[derive(Debug, Clone)]
+pub struct ProxyConfig {
+    pub addr: SocketAddr,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+/// 构建 Client，device 和 handler 必须设置
+#[derive(Default)]
+pub struct ClientBuilder {
+    device: Option<Device>,
+    protocol: Option<Protocol>,
+    handler: Option<Box<dyn Handler + Sync + Send + 'static>>,
+    timeout: Option<Duration>,
+    heartbeat_interval: Option<Duration>,
+    translate_max_retries: Option<u32>,
+    proxy: Option<ProxyConfig>,
+    tcp_recv_buffer: Option<usize>,
+    tcp_send_buffer: Option<usize>,
+    message_dedup_capacity: Option<usize>,
+}
+
+impl ClientBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn device(mut self, device: Device) -> Self {
+        self.device = Some(device);
+        self
+    }
+
+    /// 默认 IPad
+    pub fn protocol(mut self, protocol: Protocol) -> Self {
+        self.protocol = Some(protocol);
+        self
+    }
+
+    pub fn handler<H>(mut self, handler: H) -> Self
+    where
+        H: Handler + Sync + Send + 'static,
+    {
+        self.handler = Some(Box::new(handler));
+        self
+    }
+
+    /// send_and_wait 超时时间，默认 15 秒
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// 心跳间隔，默认 30 秒
+    pub fn heartbeat_interval(mut self, interval: Duration) -> Self {
+        self.heartbeat_interval = Some(interval);
+        self
+    }
+
+    /// 翻译被限流时的最大重试次数，默认 3 次
+    pub fn translate_max_retries(mut self, retries: u32) -> Self {
+        self.translate_max_retries = Some(retries);
+        self
+    }
+
+    pub fn proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    pub fn tcp_recv_buffer(mut self, size: usize) -> Self {
+        self.tcp_recv_buffer = Some(size);
+        self
+    }
+
+    pub fn tcp_send_buffer(mut self, size: usize) -> Self {
+        self.tcp_send_buffer = Some(size);
+        self
+    }
+
+    /// 群消息去重记录的消息数量，默认 1000
+    pub fn message_dedup_capacity(mut self, capacity: usize) -> Self {
+        self.message_dedup_capacity = Some(capacity);
+        self
+    }
+
+    pub fn build(self) -> RQResult<Client> {
+        let device = self
+            .device
+            .ok_or_else(|| RQError::InvalidArgument("device is not set".into()))?;
+        let handler = self
+            .handler
+            .ok_or_else(|| RQError::InvalidArgument("handler is not set".into()))?;
+        let version = get_version(self.protocol.unwrap_or(Protocol::IPad));
+        let mut client = Client::new_boxed(device, version, handler);
+        if let Some(timeout) = self.timeout {
+            client.request_timeout = timeout;
+        }
+        if let Some(interval) = self.heartbeat_interval {
+            client.heartbeat_interval = interval;
+        }
+        if let Some(retries) = self.translate_max_retries {
+            client.translate_max_retries = retries;
+        }
+        client.proxy = self.proxy;
+        client.tcp_recv_buffer = self.tcp_recv_buffer;
+        client.tcp_send_buffer = self.tcp_send_buffer;
+        if let Some(capacity) = self.message_dedup_capacity {
+            client.group_message_dedup =
+                tokio::sync::RwLock::new(cached::SizedCache::with_size(capacity.max(1)));
+        }
+        Ok(client)
+    }
+}
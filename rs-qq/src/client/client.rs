@@ -1,33 +1,65 @@
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use std::sync::Arc;
 
+use cached::Cached;
+use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::oneshot;
 use tokio::sync::RwLock;
-use tokio::time::{sleep, Duration};
+use tokio::time::{sleep, Duration, Instant};
+use tokio_util::sync::CancellationToken;
 
 use crate::engine::protocol::version::Version;
 use crate::engine::token::Token;
 
+use crate::client::event::{HeartbeatFailReason, HeartbeatFailedEvent};
+use crate::engine::command::common::PacketRequest;
 use crate::engine::protocol::{device::Device, packet::Packet};
 use crate::engine::Engine;
+use crate::handler::QEvent;
 use crate::{RQError, RQResult};
 
-use super::Client;
+use super::{Client, ProxyConfig};
+
+// 发送队列长度
+const OUT_PKT_CAPACITY: usize = 1024;
+// 未收齐的分片群消息的清理间隔
+const STALE_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
 
 impl super::Client {
+    /// 创建 Client，不需要 tokio runtime，设备信息的校验见 init
     pub fn new<H>(device: Device, version: &'static Version, handler: H) -> Client
     where
         H: crate::client::handler::Handler + 'static + Sync + Send,
     {
-        let (out_pkt_sender, _) = tokio::sync::broadcast::channel(1024);
+        Self::new_boxed(device, version, Box::new(handler))
+    }
+
+    pub(crate) fn new_boxed(
+        device: Device,
+        version: &'static Version,
+        handler: Box<dyn crate::client::handler::Handler + Sync + Send + 'static>,
+    ) -> Client {
+        let (out_pkt_sender, out_pkt_receiver) = tokio::sync::mpsc::channel(OUT_PKT_CAPACITY);
         let (disconnect_signal, _) = tokio::sync::broadcast::channel(8);
 
+        let engine = Engine::new(device, version);
         Client {
-            handler: Box::new(handler),
-            engine: RwLock::new(Engine::new(device, version)),
+            handler,
+            uin: engine.uin.clone(),
+            engine: RwLock::new(engine),
             running: AtomicBool::new(false),
             heartbeat_enabled: AtomicBool::new(false),
             online: AtomicBool::new(false),
+            shutting_down: AtomicBool::new(false),
+            inflight_requests: AtomicU32::new(0),
+            request_timeout: Duration::from_secs(15),
+            heartbeat_interval: Duration::from_secs(30),
+            translate_max_retries: crate::config::DEFAULT_TRANSLATE_MAX_RETRIES,
+            proxy: None,
+            tcp_recv_buffer: None,
+            tcp_send_buffer: None,
             out_pkt_sender,
+            out_pkt_receiver: tokio::sync::Mutex::new(out_pkt_receiver),
             disconnect_signal,
             // out_going_packet_session_id: RwLock::new(Bytes::from_static(&[0x02, 0xb0, 0x5b, 0x8b])),
             packet_promises: Default::default(),
@@ -35,16 +67,25 @@ impl super::Client {
             receipt_waiters: Default::default(),
             account_info: Default::default(),
             address: Default::default(),
+            server_address: Default::default(),
             friends: Default::default(),
             groups: Default::default(),
             online_clients: Default::default(),
+            group_count: AtomicUsize::new(0),
+            friend_count: AtomicUsize::new(0),
             last_message_time: Default::default(),
             start_time: chrono::Utc::now().timestamp() as i32,
-            group_message_builder: RwLock::new(cached::TimedCache::with_lifespan(600)),
+            group_message_builder: Default::default(),
+            stale_sweeper_running: AtomicBool::new(false),
             c2c_cache: RwLock::new(cached::TimedCache::with_lifespan(3600)),
             push_req_cache: RwLock::new(cached::TimedCache::with_lifespan(30)),
             push_trans_cache: RwLock::new(cached::TimedCache::with_lifespan(15)),
+            group_message_dedup: RwLock::new(cached::SizedCache::with_size(
+                crate::config::DEFAULT_MESSAGE_DEDUP_CAPACITY,
+            )),
+            known_message_count: AtomicUsize::new(0),
             group_sys_message_cache: RwLock::new(Default::default()),
+            gtk_cache: RwLock::new(cached::TimedCache::with_lifespan(300)),
             highway_session: RwLock::new(Default::default()),
             highway_addrs: RwLock::new(Default::default()),
         }
@@ -54,72 +95,153 @@ impl super::Client {
     where
         H: crate::client::handler::Handler + 'static + Sync + Send,
     {
-        Self::new(config.device, config.version, handler)
+        let mut client = Self::new(config.device, config.version, handler);
+        client.tcp_recv_buffer = config.tcp_recv_buffer;
+        client.tcp_send_buffer = config.tcp_send_buffer;
+        client.request_timeout = config.request_timeout;
+        client.heartbeat_interval = config.heartbeat_interval;
+        client.translate_max_retries = config.translate_max_retries;
+        client.proxy = config.proxy;
+        client.group_message_dedup = RwLock::new(cached::SizedCache::with_size(
+            config.message_dedup_capacity.max(1),
+        ));
+        client
+    }
+
+    /// Client::connect 使用的 SOCKS5 代理
+    pub fn proxy(&self) -> Option<&ProxyConfig> {
+        self.proxy.as_ref()
+    }
+
+    /// 登录前校验设备信息
+    pub async fn init(&self) -> RQResult<()> {
+        self.engine.read().await.transport.device.validate()
+    }
+
+    /// 当前账号，登录前为 0
+    pub fn uin(&self) -> i64 {
+        self.uin.load(Ordering::Relaxed)
+    }
+
+    /// 当前账号昵称
+    pub async fn nickname(&self) -> String {
+        self.account_info.read().await.nickname.clone()
     }
 
-    pub async fn uin(&self) -> i64 {
-        return self.engine.read().await.uin.load(Ordering::Relaxed);
+    /// 一次构建多个互不依赖的数据包，只获取一次 engine 的锁，返回顺序与 requests 相同
+    pub async fn build_packets(&self, requests: Vec<PacketRequest>) -> Vec<Packet> {
+        self.engine.read().await.batch_build_packets(requests)
     }
 
+    /// 发送队列已满时等待，返回写入队列的字节数
     pub async fn send(&self, pkt: Packet) -> RQResult<usize> {
+        if self.shutting_down.load(Ordering::Relaxed) {
+            return Err(RQError::ShuttingDown);
+        }
         tracing::trace!(target: "rs_qq", "sending pkt {}-{},", pkt.command_name, pkt.seq_id);
         let data = self.engine.read().await.transport.encode_packet(pkt);
+        let len = data.len();
         self.out_pkt_sender
             .send(data)
+            .await
+            .map(|_| len)
             .map_err(|_| RQError::Other("failed to send out_pkt".into()))
     }
 
+    /// 不等待的 send，适合不关心结果的通知类数据包
+    /// 发送队列已满（或正在登录、engine 被写锁占用）时直接返回 QueueFull，连接已关闭返回 Network
+    pub fn try_send(&self, pkt: Packet) -> RQResult<()> {
+        if self.shutting_down.load(Ordering::Relaxed) {
+            return Err(RQError::ShuttingDown);
+        }
+        let data = match self.engine.try_read() {
+            Ok(engine) => engine.transport.encode_packet(pkt),
+            Err(_) => return Err(RQError::QueueFull),
+        };
+        self.out_pkt_sender.try_send(data).map_err(|err| match err {
+            TrySendError::Full(_) => RQError::QueueFull,
+            TrySendError::Closed(_) => RQError::Network,
+        })
+    }
+
     pub async fn send_and_wait(&self, pkt: Packet) -> RQResult<Packet> {
+        if self.shutting_down.load(Ordering::Relaxed) {
+            return Err(RQError::ShuttingDown);
+        }
         tracing::trace!(target: "rs_qq", "send_and_waitting pkt {}-{},", pkt.command_name, pkt.seq_id);
         let seq = pkt.seq_id;
         let expect = pkt.command_name.clone();
         let data = self.engine.read().await.transport.encode_packet(pkt);
         let (sender, receiver) = oneshot::channel();
-        {
-            let mut packet_promises = self.packet_promises.write().await;
-            packet_promises.insert(seq, sender);
-        }
-        if self.out_pkt_sender.send(data).is_err() {
-            let mut packet_promises = self.packet_promises.write().await;
-            packet_promises.remove(&seq);
+        let start = Instant::now();
+        self.packet_promises.insert(seq, sender);
+        // future 被 drop 时也会清理
+        let _inflight = InflightGuard::new(self, seq);
+        if self.out_pkt_sender.send(data).await.is_err() {
             return Err(RQError::Network);
         }
-        match tokio::time::timeout(std::time::Duration::from_secs(15), receiver).await {
+        match tokio::time::timeout(self.request_timeout, receiver).await {
             Ok(p) => p.unwrap().check_command_name(&expect),
             Err(_) => {
                 tracing::trace!(target: "rs_qq", "waiting pkt {}-{} timeout", expect, seq);
-                self.packet_promises.write().await.remove(&seq);
-                Err(RQError::Timeout)
+                Err(RQError::Timeout {
+                    command: expect,
+                    elapsed: start.elapsed(),
+                })
             }
         }
     }
 
+    /// 正在等待响应的请求数量，过高说明服务器或网络较慢
+    pub fn inflight_request_count(&self) -> u32 {
+        self.inflight_requests.load(Ordering::Relaxed)
+    }
+
     pub async fn wait_packet(&self, pkt_name: &str, delay: u64) -> RQResult<Packet> {
         tracing::trace!(target: "rs_qq", "waitting pkt {}", pkt_name);
         let (tx, rx) = oneshot::channel();
-        {
-            self.packet_waiters
-                .write()
-                .await
-                .insert(pkt_name.to_owned(), tx);
-        }
+        let start = Instant::now();
+        self.packet_waiters.insert(pkt_name.to_owned(), tx);
         match tokio::time::timeout(std::time::Duration::from_secs(delay), rx).await {
             Ok(i) => Ok(i.unwrap()),
             Err(_) => {
                 tracing::trace!(target: "rs_qq", "waitting pkt {} timeout", pkt_name);
-                self.packet_waiters.write().await.remove(pkt_name);
-                Err(RQError::Timeout)
+                self.packet_waiters.remove(pkt_name);
+                Err(RQError::Timeout {
+                    command: pkt_name.to_owned(),
+                    elapsed: start.elapsed(),
+                })
             }
         }
     }
 
-    pub async fn do_heartbeat(&self) {
+    pub async fn do_heartbeat(self: &Arc<Self>) {
+        self.do_heartbeat_with_cancel(CancellationToken::new())
+            .await
+    }
+
+    /// 同 do_heartbeat，token 被取消时退出
+    pub async fn do_heartbeat_with_cancel(self: &Arc<Self>, token: CancellationToken) {
         self.heartbeat_enabled.store(true, Ordering::SeqCst);
         let mut times = 0;
         while self.online.load(Ordering::SeqCst) {
-            sleep(Duration::from_secs(30)).await;
+            tokio::select! {
+                _ = sleep(self.heartbeat_interval) => {}
+                _ = token.cancelled() => break,
+            }
             match self.heartbeat().await {
-                Err(_) => {
+                Err(err) => {
+                    // 超时可能只是服务器繁忙，发送失败说明连接已经不可用
+                    let reason = match err {
+                        RQError::Timeout { .. } => HeartbeatFailReason::Timeout,
+                        err => HeartbeatFailReason::SendFailed(err.to_string()),
+                    };
+                    self.handler
+                        .handle(QEvent::HeartbeatFailed(HeartbeatFailedEvent {
+                            client: self.clone(),
+                            reason,
+                        }))
+                        .await;
                     continue;
                 }
                 Ok(_) => {
@@ -136,6 +258,88 @@ impl super::Client {
         self.heartbeat_enabled.store(false, Ordering::SeqCst);
     }
 
+    /// 消息同步游标（秒），可自行保存，重启后通过 set_last_message_time 恢复
+    pub fn last_message_time(&self) -> i64 {
+        self.last_message_time.load(Ordering::SeqCst)
+    }
+
+    pub fn set_last_message_time(&self, time: i64) {
+        self.last_message_time.store(time, Ordering::SeqCst)
+    }
+
+    /// 缓存中的群数量，不需要获取锁
+    pub fn cached_group_count(&self) -> usize {
+        self.group_count.load(Ordering::Relaxed)
+    }
+
+    /// 缓存中的好友数量，不需要获取锁
+    pub fn cached_friend_count(&self) -> usize {
+        self.friend_count.load(Ordering::Relaxed)
+    }
+
+    /// 群消息去重记录中的消息数量，不需要获取锁
+    pub fn known_message_count(&self) -> usize {
+        self.known_message_count.load(Ordering::Relaxed)
+    }
+
+    /// 等待合并的分片群消息数量，重连不会清空
+    pub async fn pending_group_message_count(&self) -> usize {
+        self.group_message_builder.read().await.len()
+    }
+
+    // 每 60 秒清理一次未收齐的分片群消息，不依赖心跳是否开启，stop / shutdown 后退出
+    pub(crate) fn spawn_stale_message_sweeper(self: &Arc<Self>) {
+        if self.stale_sweeper_running.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let client = Arc::downgrade(self);
+        tokio::spawn(async move {
+            loop {
+                sleep(STALE_SWEEP_INTERVAL).await;
+                let client = match client.upgrade() {
+                    Some(client) => client,
+                    None => return,
+                };
+                if client.running.load(Ordering::SeqCst)
+                    && !client.shutting_down.load(Ordering::SeqCst)
+                {
+                    client.flush_stale_message_builders().await;
+                    continue;
+                }
+                client.stale_sweeper_running.store(false, Ordering::SeqCst);
+                // 在 store(false) 之前重新 start 的连接不会启动新任务，由当前任务继续清理
+                if !client.running.load(Ordering::SeqCst)
+                    || client.stale_sweeper_running.swap(true, Ordering::SeqCst)
+                {
+                    return;
+                }
+            }
+        });
+    }
+
+    /// 清理超过 5 分钟仍未收齐的分片群消息，返回清理的数量
+    pub async fn flush_stale_message_builders(&self) -> usize {
+        const STALE_SECONDS: i64 = 300;
+        let now = chrono::Utc::now().timestamp();
+        let mut builder = self.group_message_builder.write().await;
+        let before = builder.len();
+        builder.retain(|div_seq, (time, parts)| {
+            if now - *time < STALE_SECONDS {
+                return true;
+            }
+            tracing::warn!(
+                target: "rs_qq",
+                "drop incomplete group message, group: {}, div_seq: {}, parts: {}/{}",
+                parts.first().map(|p| p.group_code).unwrap_or_default(),
+                div_seq,
+                parts.len(),
+                parts.first().map(|p| p.pkg_num).unwrap_or_default()
+            );
+            false
+        });
+        before - builder.len()
+    }
+
     pub async fn gen_token(&self) -> Token {
         self.engine.read().await.gen_token()
     }
@@ -150,3 +354,59 @@ impl Drop for Client {
         self.stop();
     }
 }
+
+// 记录 send_and_wait 正在等待的请求，drop 时移除 promise 并减少计数
+struct InflightGuard<'a> {
+    client: &'a Client,
+    seq: i32,
+}
+
+impl<'a> InflightGuard<'a> {
+    fn new(client: &'a Client, seq: i32) -> Self {
+        client.inflight_requests.fetch_add(1, Ordering::Relaxed);
+        Self { client, seq }
+    }
+}
+
+impl Drop for InflightGuard<'_> {
+    fn drop(&mut self) {
+        self.client.packet_promises.remove(&self.seq);
+        self.client
+            .inflight_requests
+            .fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::protocol::version::ANDROID_WATCH;
+
+    #[tokio::test]
+    async fn test_try_send_queue_full() {
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let client = Client::new(Device::random(), ANDROID_WATCH, tx);
+        for _ in 0..OUT_PKT_CAPACITY {
+            client.try_send(Packet::default()).unwrap();
+        }
+        assert!(matches!(
+            client.try_send(Packet::default()),
+            Err(RQError::QueueFull)
+        ));
+        client.shutdown();
+        assert!(matches!(
+            client.try_send(Packet::default()),
+            Err(RQError::ShuttingDown)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_group_files_unsupported_on_watch() {
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let client = Client::new(Device::random(), ANDROID_WATCH, tx);
+        assert!(matches!(
+            client.get_group_file_count(1000).await,
+            Err(RQError::UnsupportedOnProtocol(_))
+        ));
+    }
+}
@@ -1,9 +1,14 @@
+use std::fmt;
+use std::net::SocketAddr;
 use std::sync::Arc;
 
+use bytes::Bytes;
+
 use crate::engine::command::profile_service::{JoinGroupRequest, NewFriendRequest, SelfInvited};
 use crate::engine::structs::{
-    DeleteFriend, FriendInfo, FriendMessageRecall, FriendPoke, GroupAudioMessage, GroupLeave,
-    GroupMessageRecall, GroupMute, GroupNameUpdate, MemberPermissionChange, NewMember,
+    DeleteFriend, FriendInfo, FriendMessageRecall, FriendPoke, GroupAudioMessage, GroupGift,
+    GroupHonorChanged, GroupLeave, GroupMemberCardChanged, GroupMessageRecall, GroupMute,
+    GroupNameUpdate, GroupPoke, GroupTips, GroupToss, MemberPermissionChange, NewMember,
     PrivateAudioMessage, TempMessage,
 };
 use crate::engine::{jce, RQResult};
@@ -164,6 +169,14 @@ pub struct GroupMuteEvent {
     pub group_mute: GroupMute,
 }
 
+#[derive(Clone, derivative::Derivative)]
+#[derivative(Debug)]
+pub struct GroupHonorChangedEvent {
+    #[derivative(Debug = "ignore")]
+    pub client: Arc<Client>,
+    pub honor_changed: GroupHonorChanged,
+}
+
 #[derive(Clone, derivative::Derivative)]
 #[derivative(Debug)]
 pub struct FriendMessageRecallEvent {
@@ -204,6 +217,46 @@ pub struct FriendPokeEvent {
     pub poke: FriendPoke,
 }
 
+#[derive(Clone, derivative::Derivative)]
+#[derivative(Debug)]
+pub struct GroupMemberCardChangedEvent {
+    #[derivative(Debug = "ignore")]
+    pub client: Arc<Client>,
+    pub change: GroupMemberCardChanged,
+}
+
+#[derive(Clone, derivative::Derivative)]
+#[derivative(Debug)]
+pub struct GroupPokeEvent {
+    #[derivative(Debug = "ignore")]
+    pub client: Arc<Client>,
+    pub poke: GroupPoke,
+}
+
+#[derive(Clone, derivative::Derivative)]
+#[derivative(Debug)]
+pub struct GroupTossEvent {
+    #[derivative(Debug = "ignore")]
+    pub client: Arc<Client>,
+    pub toss: GroupToss,
+}
+
+#[derive(Clone, derivative::Derivative)]
+#[derivative(Debug)]
+pub struct GroupGiftEvent {
+    #[derivative(Debug = "ignore")]
+    pub client: Arc<Client>,
+    pub gift: GroupGift,
+}
+
+#[derive(Clone, derivative::Derivative)]
+#[derivative(Debug)]
+pub struct GroupTipsEvent {
+    #[derivative(Debug = "ignore")]
+    pub client: Arc<Client>,
+    pub tips: GroupTips,
+}
+
 #[derive(Clone, derivative::Derivative)]
 #[derivative(Debug)]
 pub struct GroupNameUpdateEvent {
@@ -274,6 +327,33 @@ pub struct KickedOfflineEvent {
     #[derivative(Debug = "ignore")]
     pub client: Arc<Client>,
     pub offline: jce::RequestPushForceOffline,
+    pub kick_reason: KickReason,
+    pub kick_message: String,
+}
+
+/// 被踢下线原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KickReason {
+    /// 其他设备登录
+    AnotherDeviceLogin,
+    /// 同一设备重新登录
+    AnotherDeviceKick,
+    /// 异常下线
+    Abnormal,
+    Other(u8),
+}
+
+impl From<&jce::RequestPushForceOffline> for KickReason {
+    fn from(offline: &jce::RequestPushForceOffline) -> Self {
+        if offline.tips.is_empty() {
+            return KickReason::Abnormal;
+        }
+        match offline.same_device {
+            0 => KickReason::AnotherDeviceLogin,
+            1 => KickReason::AnotherDeviceKick,
+            other => KickReason::Other(other),
+        }
+    }
 }
 
 #[derive(Clone, derivative::Derivative)]
@@ -283,3 +363,121 @@ pub struct MSFOfflineEvent {
     pub client: Arc<Client>,
     pub offline: jce::RequestMSFForceOffline,
 }
+
+#[derive(Clone, derivative::Derivative)]
+#[derivative(Debug)]
+pub struct LoginSucceededEvent {
+    #[derivative(Debug = "ignore")]
+    pub client: Arc<Client>,
+    pub uin: i64,
+    pub nickname: String,
+}
+
+/// 登录失败原因
+#[derive(Debug, Clone)]
+pub enum LoginFailReason {
+    WrongPassword,
+    DeviceLock,
+    BannedAccount,
+    OtherError(String),
+}
+
+impl fmt::Display for LoginFailReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoginFailReason::WrongPassword => write!(f, "wrong password"),
+            LoginFailReason::DeviceLock => write!(f, "device lock verification required"),
+            LoginFailReason::BannedAccount => write!(f, "account is banned"),
+            LoginFailReason::OtherError(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+#[derive(Clone, derivative::Derivative)]
+#[derivative(Debug)]
+pub struct LoginFailedEvent {
+    #[derivative(Debug = "ignore")]
+    pub client: Arc<Client>,
+    pub reason: LoginFailReason,
+}
+
+/// 连接断开原因
+#[derive(Debug, Clone)]
+pub enum DisconnectReason {
+    /// 服务端关闭连接
+    ServerClosed,
+    /// 开启心跳后长时间没有收到任何数据
+    Timeout,
+    /// 读写错误
+    IoError(String),
+    /// 被踢下线
+    Kicked,
+    /// 调用 stop 主动断开
+    Stopped,
+}
+
+impl fmt::Display for DisconnectReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DisconnectReason::ServerClosed => write!(f, "connection closed by server"),
+            DisconnectReason::Timeout => write!(f, "connection timed out"),
+            DisconnectReason::IoError(err) => write!(f, "io error, {err}"),
+            DisconnectReason::Kicked => write!(f, "kicked offline"),
+            DisconnectReason::Stopped => write!(f, "stopped by client"),
+        }
+    }
+}
+
+#[derive(Clone, derivative::Derivative)]
+#[derivative(Debug)]
+pub struct ConnectionEstablishedEvent {
+    #[derivative(Debug = "ignore")]
+    pub client: Arc<Client>,
+    pub server_addr: SocketAddr,
+}
+
+#[derive(Clone, derivative::Derivative)]
+#[derivative(Debug)]
+pub struct ConnectionLostEvent {
+    #[derivative(Debug = "ignore")]
+    pub client: Arc<Client>,
+    pub reason: DisconnectReason,
+}
+
+/// 心跳失败原因
+#[derive(Debug, Clone)]
+pub enum HeartbeatFailReason {
+    /// 服务器未响应，可能是服务器繁忙
+    Timeout,
+    /// 数据包发送失败，连接可能已经断开
+    SendFailed(String),
+}
+
+impl fmt::Display for HeartbeatFailReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeartbeatFailReason::Timeout => write!(f, "server did not respond to heartbeat"),
+            HeartbeatFailReason::SendFailed(err) => write!(f, "failed to send heartbeat, {err}"),
+        }
+    }
+}
+
+#[derive(Clone, derivative::Derivative)]
+#[derivative(Debug)]
+pub struct HeartbeatFailedEvent {
+    #[derivative(Debug = "ignore")]
+    pub client: Arc<Client>,
+    pub reason: HeartbeatFailReason,
+}
+
+/// 收到的数据包无法解析
+#[derive(Clone, derivative::Derivative)]
+#[derivative(Debug)]
+pub struct DecodeErrorEvent {
+    #[derivative(Debug = "ignore")]
+    pub client: Arc<Client>,
+    pub command: String,
+    pub error: String,
+    #[derivative(Debug = "ignore")]
+    pub raw_body: Bytes,
+}
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use async_trait::async_trait;
 use tokio::sync::{
     broadcast::Sender as BroadcastSender,
@@ -6,9 +8,12 @@ use tokio::sync::{
 };
 
 use crate::client::event::{
-    DeleteFriendEvent, FriendMessageRecallEvent, FriendPokeEvent, FriendRequestEvent,
-    GroupAudioMessageEvent, GroupLeaveEvent, GroupMessageEvent, GroupMessageRecallEvent,
-    GroupMuteEvent, GroupNameUpdateEvent, GroupRequestEvent, KickedOfflineEvent, MSFOfflineEvent,
+    ConnectionEstablishedEvent, ConnectionLostEvent, DecodeErrorEvent, DeleteFriendEvent,
+    FriendMessageRecallEvent, FriendPokeEvent, FriendRequestEvent, GroupAudioMessageEvent,
+    GroupGiftEvent, GroupHonorChangedEvent, GroupLeaveEvent, GroupMemberCardChangedEvent,
+    GroupMessageEvent, GroupMessageRecallEvent, GroupMuteEvent, GroupNameUpdateEvent,
+    GroupPokeEvent, GroupRequestEvent, GroupTipsEvent, GroupTossEvent, HeartbeatFailedEvent,
+    KickedOfflineEvent, LoginFailedEvent, LoginSucceededEvent, MSFOfflineEvent,
     MemberPermissionChangeEvent, NewFriendEvent, NewMemberEvent, PrivateAudioMessageEvent,
     PrivateMessageEvent, SelfInvitedEvent, TempMessageEvent,
 };
@@ -17,8 +22,10 @@ use crate::client::event::{
 #[derive(Clone, derivative::Derivative)]
 #[derivative(Debug)]
 pub enum QEvent {
-    /// 登录成功事件
-    Login(i64),
+    /// 登录成功
+    LoginSucceeded(LoginSucceededEvent),
+    /// 登录失败
+    LoginFailed(LoginFailedEvent),
     /// 群消息
     GroupMessage(GroupMessageEvent),
     /// 群语音
@@ -41,6 +48,8 @@ pub enum QEvent {
     NewMember(NewMemberEvent),
     /// 成员被禁言
     GroupMute(GroupMuteEvent),
+    /// 群荣誉变更
+    GroupHonorChanged(GroupHonorChangedEvent),
     /// 好友消息撤回
     FriendMessageRecall(FriendMessageRecallEvent),
     /// 群消息撤回
@@ -53,6 +62,16 @@ pub enum QEvent {
     FriendPoke(FriendPokeEvent),
     /// 群名称修改
     GroupNameUpdate(GroupNameUpdateEvent),
+    /// 群名片修改
+    GroupMemberCardChanged(GroupMemberCardChangedEvent),
+    /// 群戳一戳
+    GroupPoke(GroupPokeEvent),
+    /// 群抛硬币
+    GroupToss(GroupTossEvent),
+    /// 群送礼物
+    GroupGift(GroupGiftEvent),
+    /// 其他群灰条提示
+    GroupTips(GroupTipsEvent),
     /// 好友删除
     DeleteFriend(DeleteFriendEvent),
     /// 群成员权限变更
@@ -63,6 +82,56 @@ pub enum QEvent {
     /// 服务端强制下线
     /// 不能用于掉线重连，掉线重连以 start 返回为准
     MSFOffline(MSFOfflineEvent),
+    /// 连接建立
+    ConnectionEstablished(ConnectionEstablishedEvent),
+    /// 连接断开
+    ConnectionLost(ConnectionLostEvent),
+    /// 心跳失败
+    HeartbeatFailed(HeartbeatFailedEvent),
+    /// 数据包解析失败
+    DecodeError(DecodeErrorEvent),
+}
+
+impl fmt::Display for QEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QEvent::LoginSucceeded(_) => write!(f, "login succeeded"),
+            QEvent::LoginFailed(e) => write!(f, "login failed, {}", e.reason),
+            QEvent::GroupMessage(_) => write!(f, "group message"),
+            QEvent::GroupAudioMessage(_) => write!(f, "group audio message"),
+            QEvent::SelfGroupMessage(_) => write!(f, "self group message"),
+            QEvent::PrivateMessage(_) => write!(f, "private message"),
+            QEvent::PrivateAudioMessage(_) => write!(f, "private audio message"),
+            QEvent::TempMessage(_) => write!(f, "temp message"),
+            QEvent::GroupRequest(_) => write!(f, "group join request"),
+            QEvent::SelfInvited(_) => write!(f, "invited to group"),
+            QEvent::FriendRequest(_) => write!(f, "friend request"),
+            QEvent::NewMember(_) => write!(f, "new group member"),
+            QEvent::GroupMute(_) => write!(f, "group mute"),
+            QEvent::GroupHonorChanged(_) => write!(f, "group honor changed"),
+            QEvent::FriendMessageRecall(_) => write!(f, "friend message recalled"),
+            QEvent::GroupMessageRecall(_) => write!(f, "group message recalled"),
+            QEvent::NewFriend(_) => write!(f, "new friend"),
+            QEvent::GroupLeave(_) => write!(f, "group leave"),
+            QEvent::FriendPoke(_) => write!(f, "friend poke"),
+            QEvent::GroupNameUpdate(_) => write!(f, "group name updated"),
+            QEvent::GroupMemberCardChanged(_) => write!(f, "group member card changed"),
+            QEvent::GroupPoke(_) => write!(f, "group poke"),
+            QEvent::GroupToss(_) => write!(f, "group toss"),
+            QEvent::GroupGift(_) => write!(f, "group gift"),
+            QEvent::GroupTips(_) => write!(f, "group tips"),
+            QEvent::DeleteFriend(_) => write!(f, "friend deleted"),
+            QEvent::MemberPermissionChange(_) => write!(f, "member permission changed"),
+            QEvent::KickedOffline(_) => write!(f, "kicked offline by another client"),
+            QEvent::MSFOffline(_) => write!(f, "forced offline by server"),
+            QEvent::ConnectionEstablished(_) => write!(f, "connection established"),
+            QEvent::ConnectionLost(e) => write!(f, "connection lost, {}", e.reason),
+            QEvent::HeartbeatFailed(e) => write!(f, "heartbeat failed, {}", e.reason),
+            QEvent::DecodeError(e) => {
+                write!(f, "failed to decode packet {}, {}", e.command, e.error)
+            }
+        }
+    }
 }
 
 /// 处理外发数据的接口
@@ -154,7 +223,8 @@ impl Handler for WatchSender<QEvent> {
 
 #[async_trait]
 pub trait PartlyHandler: Sync {
-    async fn handle_login(&self, _: i64) {}
+    async fn handle_login_succeeded(&self, _event: LoginSucceededEvent) {}
+    async fn handle_login_failed(&self, _event: LoginFailedEvent) {}
     async fn handle_group_message(&self, _event: GroupMessageEvent) {}
     async fn handle_group_audio(&self, _event: GroupAudioMessageEvent) {}
     async fn handle_self_group_message(&self, _event: GroupMessageEvent) {}
@@ -166,16 +236,26 @@ pub trait PartlyHandler: Sync {
     async fn handle_friend_request(&self, _event: FriendRequestEvent) {}
     async fn handle_new_member(&self, _event: NewMemberEvent) {}
     async fn handle_group_mute(&self, _event: GroupMuteEvent) {}
+    async fn handle_group_honor_changed(&self, _event: GroupHonorChangedEvent) {}
     async fn handle_friend_message_recall(&self, _event: FriendMessageRecallEvent) {}
     async fn handle_group_message_recall(&self, _event: GroupMessageRecallEvent) {}
     async fn handle_new_friend(&self, _event: NewFriendEvent) {}
     async fn handle_group_leave(&self, _event: GroupLeaveEvent) {}
     async fn handle_friend_poke(&self, _event: FriendPokeEvent) {}
     async fn handle_group_name_update(&self, _event: GroupNameUpdateEvent) {}
+    async fn handle_group_member_card_changed(&self, _event: GroupMemberCardChangedEvent) {}
+    async fn handle_group_poke(&self, _event: GroupPokeEvent) {}
+    async fn handle_group_toss(&self, _event: GroupTossEvent) {}
+    async fn handle_group_gift(&self, _event: GroupGiftEvent) {}
+    async fn handle_group_tips(&self, _event: GroupTipsEvent) {}
     async fn handle_delete_friend(&self, _event: DeleteFriendEvent) {}
     async fn handle_member_permission_change(&self, _event: MemberPermissionChangeEvent) {}
     async fn handle_kicked_offline(&self, _event: KickedOfflineEvent) {}
     async fn handle_msf_offline(&self, _event: MSFOfflineEvent) {}
+    async fn handle_connection_established(&self, _event: ConnectionEstablishedEvent) {}
+    async fn handle_connection_lost(&self, _event: ConnectionLostEvent) {}
+    async fn handle_heartbeat_failed(&self, _event: HeartbeatFailedEvent) {}
+    async fn handle_decode_error(&self, _event: DecodeErrorEvent) {}
 }
 
 #[async_trait]
@@ -185,7 +265,8 @@ where
 {
     async fn handle(&self, event: QEvent) {
         match event {
-            QEvent::Login(uin) => self.handle_login(uin).await,
+            QEvent::LoginSucceeded(m) => self.handle_login_succeeded(m).await,
+            QEvent::LoginFailed(m) => self.handle_login_failed(m).await,
             QEvent::GroupMessage(m) => self.handle_group_message(m).await,
             QEvent::GroupAudioMessage(m) => self.handle_group_audio(m).await,
             QEvent::SelfGroupMessage(m) => self.handle_self_group_message(m).await,
@@ -197,16 +278,26 @@ where
             QEvent::FriendRequest(m) => self.handle_friend_request(m).await,
             QEvent::NewMember(m) => self.handle_new_member(m).await,
             QEvent::GroupMute(m) => self.handle_group_mute(m).await,
+            QEvent::GroupHonorChanged(m) => self.handle_group_honor_changed(m).await,
             QEvent::FriendMessageRecall(m) => self.handle_friend_message_recall(m).await,
             QEvent::GroupMessageRecall(m) => self.handle_group_message_recall(m).await,
             QEvent::NewFriend(m) => self.handle_new_friend(m).await,
             QEvent::GroupLeave(m) => self.handle_group_leave(m).await,
             QEvent::FriendPoke(m) => self.handle_friend_poke(m).await,
             QEvent::GroupNameUpdate(m) => self.handle_group_name_update(m).await,
+            QEvent::GroupMemberCardChanged(m) => self.handle_group_member_card_changed(m).await,
+            QEvent::GroupPoke(m) => self.handle_group_poke(m).await,
+            QEvent::GroupToss(m) => self.handle_group_toss(m).await,
+            QEvent::GroupGift(m) => self.handle_group_gift(m).await,
+            QEvent::GroupTips(m) => self.handle_group_tips(m).await,
             QEvent::DeleteFriend(m) => self.handle_delete_friend(m).await,
             QEvent::MemberPermissionChange(m) => self.handle_member_permission_change(m).await,
             QEvent::KickedOffline(m) => self.handle_kicked_offline(m).await,
             QEvent::MSFOffline(m) => self.handle_msf_offline(m).await,
+            QEvent::ConnectionEstablished(m) => self.handle_connection_established(m).await,
+            QEvent::ConnectionLost(m) => self.handle_connection_lost(m).await,
+            QEvent::HeartbeatFailed(m) => self.handle_heartbeat_failed(m).await,
+            QEvent::DecodeError(m) => self.handle_decode_error(m).await,
         }
     }
 }
@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 use std::net::SocketAddr;
-use std::sync::atomic::{AtomicBool, AtomicI64};
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU32, AtomicUsize};
 use std::sync::Arc;
+use std::time::Duration;
 
+use dashmap::DashMap;
 use tokio::sync::{broadcast, RwLock};
 use tokio::sync::{oneshot, Mutex};
 
@@ -13,8 +15,10 @@ use crate::engine::structs::{AccountInfo, AddressInfo, FriendInfo, OtherClientIn
 use crate::engine::Engine;
 pub use crate::engine::Token;
 use crate::structs::Group;
+pub use builder::{ClientBuilder, ProxyConfig};
 
 mod api;
+mod builder;
 mod client;
 pub mod event;
 pub mod handler;
@@ -24,19 +28,36 @@ mod processor;
 
 pub struct Client {
     handler: Box<dyn handler::Handler + Sync + Send + 'static>,
+    // 只在登录、刷新 token 时写入，构建数据包只需要读锁，见 Engine 的说明
     engine: RwLock<Engine>,
+    // 与 engine 共享，读取时不需要锁
+    uin: Arc<AtomicI64>,
 
     // 是否正在运行（是否需要快速重连）
     pub running: AtomicBool,
     // 是否在线（是否可以快速重连）
     pub online: AtomicBool,
+    // 是否已经调用 shutdown，之后不再发送数据包
+    pub shutting_down: AtomicBool,
     // 停止网络
-    disconnect_signal: broadcast::Sender<()>,
+    disconnect_signal: broadcast::Sender<event::DisconnectReason>,
     pub heartbeat_enabled: AtomicBool,
 
     out_pkt_sender: net::OutPktSender,
-    packet_promises: RwLock<HashMap<i32, oneshot::Sender<Packet>>>,
-    packet_waiters: RwLock<HashMap<String, oneshot::Sender<Packet>>>,
+    // 由 net_loop 独占，重连时重新获取
+    out_pkt_receiver: Mutex<net::OutPktReceiver>,
+    packet_promises: DashMap<i32, oneshot::Sender<Packet>>,
+    // 正在等待响应的 send_and_wait 数量
+    inflight_requests: AtomicU32,
+    // send_and_wait 超时时间
+    request_timeout: Duration,
+    heartbeat_interval: Duration,
+    // 翻译被限流时的最大重试次数
+    translate_max_retries: u32,
+    proxy: Option<ProxyConfig>,
+    tcp_recv_buffer: Option<usize>,
+    tcp_send_buffer: Option<usize>,
+    packet_waiters: DashMap<String, oneshot::Sender<Packet>>,
     receipt_waiters: Mutex<HashMap<i32, oneshot::Sender<i32>>>,
 
     // account info
@@ -44,21 +65,36 @@ pub struct Client {
 
     // address
     pub address: RwLock<AddressInfo>,
-    pub friends: RwLock<HashMap<i64, Arc<FriendInfo>>>,
-    pub groups: RwLock<HashMap<i64, Arc<Group>>>,
+    // 当前连接的服务器地址
+    server_address: RwLock<Option<SocketAddr>>,
+    // 只在 client 内部修改，外部通过 find_group / cached_groups 等方法读取，保证和数量同步
+    friends: RwLock<HashMap<i64, Arc<FriendInfo>>>,
+    groups: RwLock<HashMap<i64, Arc<Group>>>,
     pub online_clients: RwLock<Vec<OtherClientInfo>>,
+    // 缓存中群和好友的数量，修改 groups / friends 时同步更新
+    group_count: AtomicUsize,
+    friend_count: AtomicUsize,
 
     // statics
     pub last_message_time: AtomicI64,
     pub start_time: i32,
 
-    /// 群消息 builder 寄存 <div_seq, parts> : parts is sorted by pkg_index
-    group_message_builder: RwLock<cached::TimedCache<i32, Vec<GroupMessagePart>>>,
+    /// 群消息 builder 寄存 <div_seq, (time, parts)> : time 为收到第一个分片的时间
+    /// 由 start 启动的后台任务定期清理，见 spawn_stale_message_sweeper
+    group_message_builder: RwLock<HashMap<i32, (i64, Vec<GroupMessagePart>)>>,
+    // 清理 group_message_builder 的后台任务是否在运行，避免重连后重复启动
+    stale_sweeper_running: AtomicBool,
     /// 每个 28 Byte
     c2c_cache: RwLock<cached::TimedCache<(i64, i64, i32, i64), ()>>,
     push_req_cache: RwLock<cached::TimedCache<(i16, i64), ()>>,
     push_trans_cache: RwLock<cached::TimedCache<(i32, i64), ()>>,
+    // 最近收到的群消息 <(msg_uid, pkg_index)>，重连后服务器重发的消息会被跳过
+    group_message_dedup: RwLock<cached::SizedCache<(i64, i32), ()>>,
+    // group_message_dedup 中的记录数，和缓存一起更新，读取时不需要锁
+    known_message_count: AtomicUsize,
     group_sys_message_cache: RwLock<GroupSystemMessages>,
+    // <domain, g_tk>
+    gtk_cache: RwLock<cached::TimedCache<String, i64>>,
 
     highway_session: RwLock<crate::engine::highway::Session>,
     highway_addrs: RwLock<Vec<SocketAddr>>,
@@ -1,16 +1,27 @@
-use std::net::{Ipv4Addr, SocketAddr};
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use bytes::Bytes;
 use futures::{SinkExt, StreamExt};
-use tokio::io::{AsyncRead, AsyncWrite};
-use tokio::sync::broadcast;
-use tokio_util::codec::LengthDelimitedCodec;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpSocket, TcpStream};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio_util::codec::{Framed, LengthDelimitedCodec};
 
-use super::Client;
+use super::{Client, ProxyConfig};
+use crate::client::event::{ConnectionEstablishedEvent, ConnectionLostEvent, DisconnectReason};
+use crate::handler::QEvent;
 
-pub type OutPktSender = broadcast::Sender<Bytes>;
+pub type OutPktSender = mpsc::Sender<Bytes>;
+pub type OutPktReceiver = mpsc::Receiver<Bytes>;
+
+// 读缓冲区初始容量
+const READ_BUFFER_CAPACITY: usize = 64 * 1024;
+// 传输层解包失败时 DecodeErrorEvent 的 command
+const TRANSPORT_COMMAND: &str = "transport";
 
 impl crate::Client {
     pub fn get_address(&self) -> SocketAddr {
@@ -18,57 +29,218 @@ impl crate::Client {
         SocketAddr::new(Ipv4Addr::new(114, 221, 144, 215).into(), 80)
     }
 
-    // 开始处理流数据
+    /// 校验设备信息后连接 get_address 返回的服务器，并记录实际连接的地址
+    pub async fn connect(&self) -> std::io::Result<TcpStream> {
+        self.init()
+            .await
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))?;
+        let addr = self.get_address();
+        let socket = if self.proxy.as_ref().map_or(addr, |p| p.addr).is_ipv4() {
+            TcpSocket::new_v4()?
+        } else {
+            TcpSocket::new_v6()?
+        };
+        if let Some(size) = self.tcp_recv_buffer {
+            socket.set_recv_buffer_size(size as u32)?;
+        }
+        if let Some(size) = self.tcp_send_buffer {
+            socket.set_send_buffer_size(size as u32)?;
+        }
+        let stream = match &self.proxy {
+            Some(proxy) => {
+                let mut stream = socket.connect(proxy.addr).await?;
+                socks5_handshake(&mut stream, addr, proxy).await?;
+                stream
+            }
+            None => socket.connect(addr).await?,
+        };
+        // 大量小包，关闭 Nagle 算法
+        stream.set_nodelay(true)?;
+        // 使用代理时 peer_addr 是代理地址
+        self.set_server_address(addr).await;
+        Ok(stream)
+    }
+
+    /// 当前连接的服务器地址，未连接时为 None
+    pub async fn server_address(&self) -> Option<SocketAddr> {
+        *self.server_address.read().await
+    }
+
+    /// 连接成功后记录服务器地址
+    pub async fn set_server_address(&self, addr: SocketAddr) {
+        *self.server_address.write().await = Some(addr);
+    }
+
+    // 开始处理流数据，已记录服务器地址时（通过 connect 连接）触发 ConnectionEstablished
     pub async fn start<S: AsyncRead + AsyncWrite>(self: &Arc<Self>, stream: S) {
         self.running.store(true, Ordering::Relaxed);
-        self.net_loop(stream).await; // 阻塞到断开
-        self.disconnect();
+        self.spawn_stale_message_sweeper();
+        if let Some(server_addr) = self.server_address().await {
+            self.handler
+                .handle(QEvent::ConnectionEstablished(ConnectionEstablishedEvent {
+                    client: self.clone(),
+                    server_addr,
+                }))
+                .await;
+        }
+        let reason = self.net_loop(stream).await; // 阻塞到断开
+        self.disconnect(reason.clone());
+        self.handler
+            .handle(QEvent::ConnectionLost(ConnectionLostEvent {
+                client: self.clone(),
+                reason,
+            }))
+            .await;
+        *self.server_address.write().await = None;
     }
 
     pub fn stop(&self) {
+        self.stop_with_reason(DisconnectReason::Stopped);
+    }
+
+    /// 停止并不再发送任何数据包，send 会直接返回 ShuttingDown
+    pub fn shutdown(&self) {
+        self.shutting_down.store(true, Ordering::Relaxed);
+        self.stop();
+    }
+
+    pub(crate) fn stop_with_reason(&self, reason: DisconnectReason) {
         self.running.store(false, Ordering::Relaxed);
-        self.disconnect();
+        self.disconnect(reason);
     }
 
-    fn disconnect(&self) {
-        // TODO dispatch disconnect event
+    fn disconnect(&self, reason: DisconnectReason) {
         // don't unwrap (Err means there is no receiver.)
-        self.disconnect_signal.send(()).ok();
+        self.disconnect_signal.send(reason).ok();
     }
 
-    async fn net_loop<S: AsyncRead + AsyncWrite>(self: &Arc<Client>, stream: S) {
-        let (mut write_half, mut read_half) = LengthDelimitedCodec::builder()
+    async fn net_loop<S: AsyncRead + AsyncWrite>(
+        self: &Arc<Client>,
+        stream: S,
+    ) -> DisconnectReason {
+        // Framed 复用同一个读缓冲区，按长度前缀 split_to 出完整帧，预留容量避免频繁扩容
+        let codec = LengthDelimitedCodec::builder()
             .length_field_length(4)
             .length_adjustment(-4)
-            .new_framed(stream)
-            .split();
+            .new_codec();
+        let (mut write_half, mut read_half) =
+            Framed::with_capacity(stream, codec, READ_BUFFER_CAPACITY).split();
         let cli = self.clone();
-        let mut rx = self.out_pkt_sender.subscribe();
+        // 同一时间只有一个连接持有接收端
+        let mut rx = self.out_pkt_receiver.lock().await;
         let mut disconnect_signal = self.disconnect_signal.subscribe();
+        // 开启心跳后服务端至少每个心跳周期会回包，超过读超时没有数据视为连接已失效
+        let read_timeout = self.heartbeat_interval * 2 + self.request_timeout;
+        let read_deadline = tokio::time::sleep(read_timeout);
+        tokio::pin!(read_deadline);
         loop {
             tokio::select! {
                 input = read_half.next() => {
-                    if let Some(Ok(mut input)) = input {
-                        if let Ok(pkt)=cli.engine.read().await.transport.decode_packet(&mut input){
-                            cli.process_income_packet(pkt).await;
-                        }else {
-                            break;
+                    read_deadline.as_mut().reset(Instant::now() + read_timeout);
+                    match input {
+                        Some(Ok(input)) => {
+                            let input = input.freeze();
+                            let pkt = cli.engine.read().await.transport.decode_packet(input.clone());
+                            match pkt {
+                                Ok(pkt) => cli.process_income_packet(pkt).await,
+                                // 单个数据包解析失败不影响连接，包头解析失败时还不知道 command
+                                Err(err) => cli.report_raw_decode_error(TRANSPORT_COMMAND, err, input).await,
+                            }
                         }
-                    }else {
-                        break;
+                        Some(Err(err)) => return DisconnectReason::IoError(err.to_string()),
+                        None => return DisconnectReason::ServerClosed,
                     }
                 }
-                output = rx.recv() => {
-                    if let Ok(output) = output {
-                        if write_half.send(output).await.is_err(){
-                            break;
-                        }
+                Some(output) = rx.recv() => {
+                    if let Err(err) = write_half.send(output).await {
+                        return DisconnectReason::IoError(err.to_string());
                     }
                 }
-                _ = disconnect_signal.recv() => {
-                    break;
+                reason = disconnect_signal.recv() => {
+                    return reason.unwrap_or(DisconnectReason::Stopped);
+                }
+                _ = &mut read_deadline => {
+                    if self.heartbeat_enabled.load(Ordering::Relaxed) {
+                        return DisconnectReason::Timeout;
+                    }
+                    read_deadline.as_mut().reset(Instant::now() + read_timeout);
                 }
             }
         }
     }
 }
+
+// SOCKS5 握手 (RFC 1928 / RFC 1929)，成功后 stream 即为到 target 的连接
+async fn socks5_handshake(
+    stream: &mut TcpStream,
+    target: SocketAddr,
+    proxy: &ProxyConfig,
+) -> io::Result<()> {
+    let proxy_err = |msg: String| io::Error::new(io::ErrorKind::Other, msg);
+    let credentials = proxy.username.as_deref().map(|username| {
+        (
+            username.as_bytes(),
+            proxy.password.as_deref().unwrap_or_default().as_bytes(),
+        )
+    });
+    // 协商认证方式 0x00 无认证 0x02 用户名密码
+    match credentials {
+        Some(_) => stream.write_all(&[0x05, 0x02, 0x00, 0x02]).await?,
+        None => stream.write_all(&[0x05, 0x01, 0x00]).await?,
+    }
+    let mut reply = [0u8; 2];
+    stream.read_exact(&mut reply).await?;
+    match (reply, credentials) {
+        ([0x05, 0x00], _) => {}
+        ([0x05, 0x02], Some((username, password))) => {
+            if username.len() > 255 || password.len() > 255 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "socks5 username or password is too long",
+                ));
+            }
+            let mut req = vec![0x01, username.len() as u8];
+            req.extend_from_slice(username);
+            req.push(password.len() as u8);
+            req.extend_from_slice(password);
+            stream.write_all(&req).await?;
+            stream.read_exact(&mut reply).await?;
+            if reply[1] != 0x00 {
+                return Err(proxy_err("socks5 authentication failed".into()));
+            }
+        }
+        _ => return Err(proxy_err(format!("socks5 method rejected: {reply:?}"))),
+    }
+    // CONNECT
+    let mut req = vec![0x05, 0x01, 0x00];
+    match target.ip() {
+        IpAddr::V4(ip) => {
+            req.push(0x01);
+            req.extend_from_slice(&ip.octets());
+        }
+        IpAddr::V6(ip) => {
+            req.push(0x04);
+            req.extend_from_slice(&ip.octets());
+        }
+    }
+    req.extend_from_slice(&target.port().to_be_bytes());
+    stream.write_all(&req).await?;
+    let mut head = [0u8; 4];
+    stream.read_exact(&mut head).await?;
+    if head[1] != 0x00 {
+        return Err(proxy_err(format!(
+            "socks5 connect failed, reply {}",
+            head[1]
+        )));
+    }
+    // 跳过 BND.ADDR 和 BND.PORT
+    let addr_len = match head[3] {
+        0x01 => 4,
+        0x04 => 16,
+        0x03 => stream.read_u8().await? as usize,
+        t => return Err(proxy_err(format!("socks5 unknown address type {t}"))),
+    };
+    let mut bound = vec![0u8; addr_len + 2];
+    stream.read_exact(&mut bound).await?;
+    Ok(())
+}
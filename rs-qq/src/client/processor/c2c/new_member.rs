@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use crate::client::event::NewMemberEvent;
 use crate::engine::common::group_uin2code;
-use crate::engine::structs::NewMember;
+use crate::engine::structs::{JoinMethod, NewMember};
 use crate::engine::{pb, RQError, RQResult};
 use crate::handler::QEvent;
 use crate::Client;
@@ -17,13 +17,18 @@ impl Client {
             .ok_or_else(|| RQError::Other("missing head".into()))?;
         let group_code = group_uin2code(head.from_uin());
         let member_uin = head.auth_uin();
+        let join_method = msg
+            .body
+            .as_ref()
+            .map(|body| JoinMethod::from_join_content(body.msg_content()))
+            .unwrap_or_default();
 
         let group = self
             .find_group(group_code, true)
             .await
             .ok_or_else(|| RQError::Other("group not found".into()))?;
 
-        if member_uin == self.uin().await {
+        if member_uin == self.uin() {
             // find_group 的时候已经 reload group info 了
             self.handler
                 .handle(QEvent::NewMember(NewMemberEvent {
@@ -31,6 +36,7 @@ impl Client {
                     new_member: NewMember {
                         group_code,
                         member_uin,
+                        join_method: join_method.clone(),
                     },
                 }))
                 .await;
@@ -46,6 +52,7 @@ impl Client {
                     new_member: NewMember {
                         group_code,
                         member_uin,
+                        join_method,
                     },
                 }))
                 .await;
@@ -27,7 +27,7 @@ impl Client {
         }
 
         let private_message = parse_private_message(msg)?;
-        if private_message.from_uin == self.uin().await {
+        if private_message.from_uin == self.uin() {
             // TODO dispatch self private message event
             // TODO swap friend seq
             return Ok(());
@@ -13,7 +13,7 @@ impl Client {
         msg: pb::msg::Message,
     ) -> RQResult<()> {
         let message = parse_temp_message(msg)?;
-        if message.from_uin == self.uin().await {
+        if message.from_uin == self.uin() {
             // TODO dispatch self temp message event
             // TODO swap friend seq
             return Ok(());
@@ -30,7 +30,7 @@ impl Client {
                 if let Some(rsp_body) = rsp_body {
                     session.sig_session = Bytes::from(rsp_body.sig_session.unwrap_or_default());
                     session.session_key = Bytes::from(rsp_body.session_key.unwrap_or_default());
-                    session.uin = self.uin().await;
+                    session.uin = self.uin();
                     session.app_id = self.engine.read().await.transport.version.app_id as i32;
                     let mut highway_addrs = self.highway_addrs.write().await;
                     rsp_body.addrs.into_iter().for_each(|addr| {
@@ -1,13 +1,13 @@
 use std::sync::Arc;
 
 use cached::Cached;
-use futures::{stream, StreamExt};
+use futures::{Stream, StreamExt};
 
 use crate::engine::{jce, pb};
 
-use crate::client::event::KickedOfflineEvent;
+use crate::client::event::{DisconnectReason, KickReason, KickedOfflineEvent};
 use crate::handler::QEvent;
-use crate::Client;
+use crate::{Client, RQResult};
 
 impl Client {
     pub(crate) async fn process_push_notify(self: &Arc<Self>, notify: jce::RequestPushNotify) {
@@ -39,33 +39,37 @@ impl Client {
             }
         }
         // pull private msg and other, then process
-        let all_message = self.sync_all_message().await;
-        match all_message {
-            Ok(msgs) => {
-                self.process_message_sync(msgs).await;
-            }
-            Err(err) => {
-                tracing::warn!("failed to sync message {}", err);
-            }
-        }
+        self.process_message_sync(self.sync_all_message(None)).await;
     }
 
     pub(crate) async fn process_push_force_offline(
         self: &Arc<Self>,
         offline: jce::RequestPushForceOffline,
     ) {
-        self.stop();
+        self.stop_with_reason(DisconnectReason::Kicked);
         self.handler
             .handle(QEvent::KickedOffline(KickedOfflineEvent {
                 client: self.clone(),
+                kick_reason: KickReason::from(&offline),
+                kick_message: offline.tips.clone(),
                 offline,
             }))
             .await;
     }
 
-    pub(crate) async fn process_message_sync(self: &Arc<Self>, msgs: Vec<pb::msg::Message>) {
-        stream::iter(msgs)
+    pub(crate) async fn process_message_sync(
+        self: &Arc<Self>,
+        msgs: impl Stream<Item = RQResult<pb::msg::Message>>,
+    ) {
+        msgs
             .filter_map(|msg| async {
+                let msg = match msg {
+                    Ok(msg) => msg,
+                    Err(err) => {
+                        tracing::warn!("failed to sync message {}", err);
+                        return None;
+                    }
+                };
                 let head = msg.head.clone().unwrap();
                 if self.msg_exists(&head).await {
                     None
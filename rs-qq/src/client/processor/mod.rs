@@ -2,7 +2,11 @@ use std::sync::Arc;
 
 use bytes::Bytes;
 
+use crate::client::event::DecodeErrorEvent;
+use crate::engine::commands;
 use crate::engine::protocol::packet::Packet;
+use crate::handler::QEvent;
+use crate::RQError;
 
 pub mod c2c;
 pub mod config_push_svc;
@@ -12,6 +16,19 @@ pub mod reg_prxy_svc;
 pub mod stat_svc;
 pub mod wtlogin;
 
+// 解包失败时触发 DecodeError 事件，并结束当前处理
+macro_rules! try_decode {
+    ($cli: expr, $pkt: expr, $decoded: expr) => {
+        match $decoded {
+            Ok(v) => v,
+            Err(err) => {
+                $cli.report_decode_error(&$pkt, err).await;
+                return;
+            }
+        }
+    };
+}
+
 macro_rules! log_error {
     ($process: expr, $info: expr) => {
         if let Err(e) = $process {
@@ -21,18 +38,41 @@ macro_rules! log_error {
 }
 
 impl super::Client {
+    async fn report_decode_error(self: &Arc<Self>, pkt: &Packet, err: RQError) {
+        self.report_raw_decode_error(&pkt.command_name, err, pkt.body.clone())
+            .await;
+    }
+
+    // 推送中的子消息或传输层解包失败时使用，raw_body 为无法解析的原始数据
+    pub(super) async fn report_raw_decode_error(
+        self: &Arc<Self>,
+        command: &str,
+        err: RQError,
+        raw_body: Bytes,
+    ) {
+        tracing::warn!(target: "rs_qq", "failed to decode pkt {}: {}", command, err);
+        self.handler
+            .handle(QEvent::DecodeError(DecodeErrorEvent {
+                client: self.clone(),
+                command: command.to_owned(),
+                error: err.to_string(),
+                raw_body,
+            }))
+            .await;
+    }
+
     pub async fn process_income_packet(self: &Arc<Self>, pkt: Packet) {
         tracing::trace!(target: "rs_qq", "received pkt: {}", &pkt.command_name);
         // response
         {
-            if let Some(sender) = self.packet_promises.write().await.remove(&pkt.seq_id) {
+            if let Some((_, sender)) = self.packet_promises.remove(&pkt.seq_id) {
                 sender.send(pkt).unwrap(); //todo response
                 return;
             }
         }
         tracing::trace!(target: "rs_qq", "pkt: {} passed packet_promises", &pkt.command_name);
         {
-            if let Some(tx) = self.packet_waiters.write().await.remove(&pkt.command_name) {
+            if let Some((_, tx)) = self.packet_waiters.remove(&pkt.command_name) {
                 tx.send(pkt).unwrap();
                 return;
             }
@@ -42,64 +82,66 @@ impl super::Client {
         let cli = self.clone();
         tokio::spawn(async move {
             match pkt.command_name.as_ref() {
-                "OnlinePush.PbPushGroupMsg" => {
-                    let p = cli
-                        .engine
-                        .read()
-                        .await
-                        .decode_group_message_packet(pkt.body)
-                        .unwrap();
+                commands::ONLINE_PUSH_PB_PUSH_GROUP_MSG => {
+                    let p = try_decode!(
+                        cli,
+                        pkt,
+                        cli.engine
+                            .read()
+                            .await
+                            .decode_group_message_packet(pkt.body.clone())
+                    );
                     log_error!(
                         cli.process_group_message_part(p).await,
                         "process group message part error: {:?}"
                     )
                 }
-                "ConfigPushSvc.PushReq" => {
-                    let req = cli
-                        .engine
-                        .read()
-                        .await
-                        .decode_push_req_packet(pkt.body)
-                        .unwrap();
+                commands::CONFIG_PUSH_SVC_PUSH_REQ => {
+                    let req = try_decode!(
+                        cli,
+                        pkt,
+                        cli.engine
+                            .read()
+                            .await
+                            .decode_push_req_packet(pkt.body.clone())
+                    );
                     log_error!(
                         cli.process_config_push_req(req).await,
                         "process config push req error: {:?}"
                     )
                 }
-                "RegPrxySvc.PushParam" => {
-                    let other_clients = cli
-                        .engine
-                        .read()
-                        .await
-                        .decode_push_param_packet(&pkt.body)
-                        .unwrap();
+                commands::REG_PRXY_SVC_PUSH_PARAM => {
+                    let other_clients = try_decode!(
+                        cli,
+                        pkt,
+                        cli.engine.read().await.decode_push_param_packet(&pkt.body)
+                    );
                     log_error!(
                         cli.process_push_param(other_clients).await,
                         "process push param error: {:?}"
                     )
                 }
-                "MessageSvc.PushNotify" => {
+                commands::MESSAGE_SVC_PUSH_NOTIFY => {
                     // c2c流程：
                     // 1. Server 发送 PushNotify 到 Client, 表示有通知需要 Client 拉取 (不带具体内容)
                     // 2. Client 根据 msg_type 发送请求拉取具体通知内容
                     // 类型：好友申请、群申请、私聊消息、其他?
-                    let resp = cli.engine.read().await.decode_svc_notify(pkt.body);
-                    match resp {
-                        Ok(notify) => {
-                            cli.process_push_notify(notify).await;
-                        }
-                        Err(err) => {
-                            tracing::error!(target: "rs_qq", "failed to decode push_notify: {}", err);
-                        }
-                    }
+                    let notify = try_decode!(
+                        cli,
+                        pkt,
+                        cli.engine.read().await.decode_svc_notify(pkt.body.clone())
+                    );
+                    cli.process_push_notify(notify).await;
                 }
-                "OnlinePush.ReqPush" => {
-                    let resp = cli
-                        .engine
-                        .read()
-                        .await
-                        .decode_online_push_req_packet(pkt.body)
-                        .unwrap();
+                commands::ONLINE_PUSH_REQ_PUSH => {
+                    let resp = try_decode!(
+                        cli,
+                        pkt,
+                        cli.engine
+                            .read()
+                            .await
+                            .decode_online_push_req_packet(pkt.body.clone())
+                    );
                     let _ = cli
                         .send(cli.engine.read().await.build_delete_online_push_packet(
                             resp.uin,
@@ -111,56 +153,64 @@ impl super::Client {
                         .await;
                     cli.process_push_req(resp.msg_infos).await;
                 }
-                "OnlinePush.PbPushTransMsg" => {
-                    let online_push_trans = cli
-                        .engine
-                        .read()
-                        .await
-                        .decode_online_push_trans_packet(pkt.body)
-                        .unwrap();
+                commands::ONLINE_PUSH_PB_PUSH_TRANS_MSG => {
+                    let online_push_trans = try_decode!(
+                        cli,
+                        pkt,
+                        cli.engine
+                            .read()
+                            .await
+                            .decode_online_push_trans_packet(pkt.body.clone())
+                    );
                     cli.process_push_trans(online_push_trans).await;
                 }
-                "MessageSvc.PushForceOffline" => {
-                    let offline = cli
-                        .engine
-                        .read()
-                        .await
-                        .decode_force_offline(pkt.body)
-                        .unwrap();
+                commands::MESSAGE_SVC_PUSH_FORCE_OFFLINE => {
+                    let offline = try_decode!(
+                        cli,
+                        pkt,
+                        cli.engine
+                            .read()
+                            .await
+                            .decode_force_offline(pkt.body.clone())
+                    );
                     cli.process_push_force_offline(offline).await;
                 }
-                "StatSvc.ReqMSFOffline" => {
-                    let offline = cli
-                        .engine
-                        .read()
-                        .await
-                        .decode_msf_force_offline(pkt.body)
-                        .unwrap();
+                commands::STAT_SVC_REQ_MSF_OFFLINE => {
+                    let offline = try_decode!(
+                        cli,
+                        pkt,
+                        cli.engine
+                            .read()
+                            .await
+                            .decode_msf_force_offline(pkt.body.clone())
+                    );
                     cli.process_msf_force_offline(offline).await;
                 }
-                "OnlinePush.PbC2CMsgSync" => {
+                commands::ONLINE_PUSH_PB_C2C_MSG_SYNC => {
                     // 其他设备发送消息，同步
-                    let push = cli
-                        .engine
-                        .read()
-                        .await
-                        .decode_c2c_sync_packet(pkt.body)
-                        .unwrap();
+                    let push = try_decode!(
+                        cli,
+                        pkt,
+                        cli.engine
+                            .read()
+                            .await
+                            .decode_c2c_sync_packet(pkt.body.clone())
+                    );
                     log_error!(
                         cli.process_c2c_sync(pkt.seq_id, push).await,
                         "process group message part error: {:?}"
                     )
                 }
-                "OnlinePush.SidTicketExpired" => {
+                commands::ONLINE_PUSH_SID_TICKET_EXPIRED => {
                     log_error!(
                         cli.process_sid_ticket_expired(pkt.seq_id).await,
                         "process_sid_ticket_expired error: {:?}"
                     )
                 }
-                "RegPrxySvc.GetMsgV2"
-                | "RegPrxySvc.PbGetMsg"
-                | "RegPrxySvc.NoticeEnd"
-                | "MessageSvc.PushReaded" => {
+                commands::REG_PRXY_SVC_GET_MSG_V2
+                | commands::REG_PRXY_SVC_PB_GET_MSG
+                | commands::REG_PRXY_SVC_NOTICE_END
+                | commands::MESSAGE_SVC_PUSH_READED => {
                     tracing::trace!(target: "rs_qq", "ignore pkt: {}", &pkt.command_name);
                 }
                 _ => {
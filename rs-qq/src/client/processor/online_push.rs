@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use bytes::{Buf, Bytes};
@@ -6,8 +8,10 @@ use futures::{stream, StreamExt};
 
 use crate::client::event::{
     DeleteFriendEvent, FriendMessageRecallEvent, FriendPokeEvent, GroupAudioMessageEvent,
-    GroupLeaveEvent, GroupMessageEvent, GroupMessageRecallEvent, GroupMuteEvent,
-    GroupNameUpdateEvent, MemberPermissionChangeEvent, NewFriendEvent, NewMemberEvent,
+    GroupGiftEvent, GroupHonorChangedEvent, GroupLeaveEvent, GroupMemberCardChangedEvent,
+    GroupMessageEvent, GroupMessageRecallEvent, GroupMuteEvent, GroupNameUpdateEvent,
+    GroupPokeEvent, GroupTipsEvent, GroupTossEvent, MemberPermissionChangeEvent, NewFriendEvent,
+    NewMemberEvent,
 };
 use crate::client::handler::QEvent;
 use crate::client::Client;
@@ -18,18 +22,96 @@ use crate::engine::msg::MessageChain;
 use crate::engine::pb::msg;
 use crate::engine::structs::{
     DeleteFriend, FriendInfo, FriendMessageRecall, FriendPoke, GroupAudio, GroupAudioMessage,
-    GroupLeave, GroupMessage, GroupMessageRecall, GroupMute, GroupNameUpdate, NewMember,
+    GroupGift, GroupHonorChanged, GroupLeave, GroupMemberCardChanged, GroupMemberPermission,
+    GroupMessage, GroupMessageRecall, GroupMute, GroupNameUpdate, GroupPoke, GroupTips, GroupToss,
+    HonourType, JoinMethod, MemberPermissionChange, NewMember,
 };
-use crate::engine::{jce, pb};
+use crate::engine::{commands, jce, pb};
 use crate::{RQError, RQResult};
 
+// 群互动灰条 (busi_type 12) 的 busi_id，其他互动灰条作为 GroupTips 上报
+const GRAY_TIP_BUSI_ID_POKE: u64 = 1061;
+const GRAY_TIP_BUSI_ID_GIFT: u64 = 1068;
+const GRAY_TIP_BUSI_ID_TOSS: u64 = 1120;
+
 impl Client {
+    // 更新缓存中的群荣誉，龙王同一时间只有一人，返回实际发生的变更
+    // 群或成员不在缓存中、成员已有该荣誉时不产生变更
+    async fn update_member_honor(
+        &self,
+        group_code: i64,
+        member_uin: i64,
+        honour_type: HonourType,
+    ) -> Vec<GroupHonorChanged> {
+        let mut changes = Vec::new();
+        let group = match self.find_group(group_code, false).await {
+            Some(group) => group,
+            None => return changes,
+        };
+        let mut members = group.members.write().await;
+        match members.iter_mut().find(|m| m.uin == member_uin) {
+            Some(m) if !m.honor.contains(&honour_type) => m.honor.push(honour_type),
+            _ => return changes,
+        }
+        if honour_type == HonourType::Talkative {
+            for m in members
+                .iter_mut()
+                .filter(|m| m.uin != member_uin && m.honor.contains(&honour_type))
+            {
+                m.honor.retain(|h| *h != honour_type);
+                changes.push(GroupHonorChanged {
+                    group_code,
+                    member_uin: m.uin,
+                    honour_type,
+                    is_gained: false,
+                });
+            }
+        }
+        changes.push(GroupHonorChanged {
+            group_code,
+            member_uin,
+            honour_type,
+            is_gained: true,
+        });
+        changes
+    }
+
+    // 消息中的群名片和缓存不同时更新缓存
+    async fn update_member_card(self: &Arc<Self>, group_code: i64, member_uin: i64, card: String) {
+        let group = match self.groups.read().await.get(&group_code).cloned() {
+            Some(group) => group,
+            None => return,
+        };
+        let old_card = {
+            let mut members = group.members.write().await;
+            match members.iter_mut().find(|m| m.uin == member_uin) {
+                Some(member) if member.card_name != card => {
+                    std::mem::replace(&mut member.card_name, card.clone())
+                }
+                _ => return,
+            }
+        };
+        self.handler
+            .handle(QEvent::GroupMemberCardChanged(
+                GroupMemberCardChangedEvent {
+                    client: self.clone(),
+                    change: GroupMemberCardChanged {
+                        group_code,
+                        member_uin,
+                        old_card,
+                        new_card: card,
+                    },
+                },
+            ))
+            .await;
+    }
+
     pub(crate) async fn process_group_message_part(
         self: &Arc<Self>,
         group_message_part: GroupMessagePart,
     ) -> Result<(), RQError> {
         // receipt message
-        if group_message_part.from_uin == self.uin().await {
+        if group_message_part.from_uin == self.uin() {
             if let Some(tx) = self
                 .receipt_waiters
                 .lock()
@@ -41,6 +123,30 @@ impl Client {
             return Ok(());
         }
 
+        // 重连后服务器可能重发已经处理过的消息，跳过并重新标记已读，避免继续重发
+        if group_message_part.msg_uid != 0 {
+            let key = (group_message_part.msg_uid, group_message_part.pkg_index);
+            let duplicate = {
+                let mut dedup = self.group_message_dedup.write().await;
+                let duplicate = dedup.cache_get(&key).is_some();
+                if !duplicate {
+                    dedup.cache_set(key, ());
+                    self.known_message_count
+                        .store(dedup.cache_size(), Ordering::Relaxed);
+                }
+                duplicate
+            };
+            if duplicate {
+                tracing::debug!(target: "rs_qq", "skip duplicate group message {:?}", key);
+                return self
+                    .mark_group_message_readed(
+                        group_message_part.group_code,
+                        group_message_part.seq,
+                    )
+                    .await;
+            }
+        }
+
         if let Some(ptt) = group_message_part.ptt {
             self.handler
                 .handle(QEvent::GroupAudioMessage(GroupAudioMessageEvent {
@@ -62,19 +168,17 @@ impl Client {
         let pkg_num = group_message_part.pkg_num;
         let group_msg = if pkg_num > 1 {
             let mut builder = self.group_message_builder.write().await;
-            if builder.cache_misses().unwrap_or_default() > 100 {
-                builder.flush();
-                builder.cache_reset_metrics();
-            }
             // muti-part
             let div_seq = group_message_part.div_seq;
-            let parts = builder.cache_get_or_set_with(div_seq, Vec::new);
+            let (_, parts) = builder
+                .entry(div_seq)
+                .or_insert_with(|| (chrono::Utc::now().timestamp(), Vec::new()));
             parts.push(group_message_part);
             if parts.len() < pkg_num as usize {
                 // wait for more parts
                 None
             } else {
-                Some(builder.cache_remove(&div_seq).unwrap_or_default())
+                builder.remove(&div_seq).map(|(_, parts)| parts)
             }
         } else {
             // single-part
@@ -84,6 +188,10 @@ impl Client {
         // handle message
         if let Some(group_msg) = group_msg {
             // message is finish
+            if let Some(card) = group_msg.first().and_then(|p| p.group_card.clone()) {
+                let (group_code, member_uin) = (group_msg[0].group_code, group_msg[0].from_uin);
+                self.update_member_card(group_code, member_uin, card).await;
+            }
             self.handler
                 .handle(QEvent::GroupMessage(GroupMessageEvent {
                     client: self.clone(),
@@ -102,6 +210,7 @@ impl Client {
         let group_message = GroupMessage {
             seqs: parts.iter().map(|p| p.seq).collect(),
             rands: parts.iter().map(|p| p.rand).collect(),
+            msg_uid: parts.first().map(|p| p.msg_uid).unwrap_or_default(),
             group_code: parts.first().map(|p| p.group_code).unwrap_or_default(),
             from_uin: parts.first().map(|p| p.from_uin).unwrap_or_default(),
             time: parts.first().map(|p| p.time).unwrap_or_default(),
@@ -113,7 +222,6 @@ impl Client {
             ),
         };
         //todo extInfo
-        //todo group_card_update
         //todo ptt
         Ok(group_message)
     }
@@ -132,12 +240,19 @@ impl Client {
                     match i_type {
                         0x0c => {
                             let operator = r.get_u32() as i64;
-                            if operator == self.uin().await {
+                            if operator == self.uin() {
                                 continue;
                             }
                             r.advance(6);
                             let target = r.get_u32() as i64;
                             let time = r.get_u32();
+                            if target == 0 {
+                                // 全员禁言，time 不为 0 表示开启
+                                self.modify_cached_group_info(group_code, |info| {
+                                    info.shut_up_timestamp = time as i64
+                                })
+                                .await;
+                            }
                             self.handler
                                 .handle(QEvent::GroupMute(GroupMuteEvent {
                                     client: self.clone(),
@@ -181,6 +296,94 @@ impl Client {
                                     })
                                     .await;
                             }
+                            if let Some(gray_tip) = b.opt_general_gray_tip {
+                                let params: HashMap<String, String> = gray_tip
+                                    .msg_templ_param
+                                    .iter()
+                                    .map(|p| (p.name.clone(), p.value.clone()))
+                                    .collect();
+                                let param_uin = |name: &str| {
+                                    params
+                                        .get(name)
+                                        .and_then(|v| v.parse::<i64>().ok())
+                                        .unwrap_or_default()
+                                };
+                                let param_str =
+                                    |name: &str| params.get(name).cloned().unwrap_or_default();
+                                let honour_type =
+                                    HonourType::from_gray_tip(gray_tip.templ_id, &gray_tip.content);
+                                let member_uin = param_uin("uin");
+                                // busi_type 12 为群互动灰条，按 busi_id 区分
+                                let interactive = gray_tip.busi_type == 12;
+                                if interactive && gray_tip.busi_id == GRAY_TIP_BUSI_ID_POKE {
+                                    // 戳一戳
+                                    self.handler
+                                        .handle(QEvent::GroupPoke(GroupPokeEvent {
+                                            client: self.clone(),
+                                            poke: GroupPoke {
+                                                group_code,
+                                                sender: param_uin("uin_str1"),
+                                                receiver: param_uin("uin_str2"),
+                                                action_text: param_str("action_str"),
+                                                suffix: param_str("suffix_str"),
+                                            },
+                                        }))
+                                        .await;
+                                } else if interactive && gray_tip.busi_id == GRAY_TIP_BUSI_ID_TOSS {
+                                    self.handler
+                                        .handle(QEvent::GroupToss(GroupTossEvent {
+                                            client: self.clone(),
+                                            toss: GroupToss {
+                                                group_code,
+                                                operator: param_uin("uin_str1"),
+                                                result: param_str("suffix_str"),
+                                            },
+                                        }))
+                                        .await;
+                                } else if interactive && gray_tip.busi_id == GRAY_TIP_BUSI_ID_GIFT {
+                                    self.handler
+                                        .handle(QEvent::GroupGift(GroupGiftEvent {
+                                            client: self.clone(),
+                                            gift: GroupGift {
+                                                group_code,
+                                                sender: param_uin("uin_str1"),
+                                                receiver: param_uin("uin_str2"),
+                                                gift_name: param_str("suffix_str"),
+                                            },
+                                        }))
+                                        .await;
+                                } else if matches!(honour_type, HonourType::Unknown(_))
+                                    || member_uin == 0
+                                {
+                                    self.handler
+                                        .handle(QEvent::GroupTips(GroupTipsEvent {
+                                            client: self.clone(),
+                                            tips: GroupTips {
+                                                group_code,
+                                                busi_type: gray_tip.busi_type,
+                                                busi_id: gray_tip.busi_id,
+                                                templ_id: gray_tip.templ_id,
+                                                params,
+                                                content: gray_tip.content,
+                                            },
+                                        }))
+                                        .await;
+                                } else {
+                                    for honor_changed in self
+                                        .update_member_honor(group_code, member_uin, honour_type)
+                                        .await
+                                    {
+                                        self.handler
+                                            .handle(QEvent::GroupHonorChanged(
+                                                GroupHonorChangedEvent {
+                                                    client: self.clone(),
+                                                    honor_changed,
+                                                },
+                                            ))
+                                            .await;
+                                    }
+                                }
+                            }
                             // TODO 一些没什么用的 event 暂时没写
                         }
                         _ => {}
@@ -233,29 +436,47 @@ impl Client {
                                     client: self.clone(),
                                     leave: GroupLeave {
                                         group_code: d4.uin,
-                                        member_uin: self.uin().await,
+                                        member_uin: self.uin(),
+                                        was_kicked: false,
                                         operator_uin: None,
                                     },
                                 }))
                                 .await;
                         }
                         0x122 | 0x123 => {
-                            let t = pb::notify::GeneralGrayTipInfo::from_bytes(&msg.v_protobuf)
-                                .unwrap();
-                            let mut sender: i64 = 0;
-                            let mut receiver: i64 = 0;
+                            let t =
+                                match pb::notify::GeneralGrayTipInfo::from_bytes(&msg.v_protobuf) {
+                                    Ok(t) => t,
+                                    Err(err) => {
+                                        self.report_raw_decode_error(
+                                            commands::ONLINE_PUSH_REQ_PUSH,
+                                            err.into(),
+                                            msg.v_protobuf,
+                                        )
+                                        .await;
+                                        continue;
+                                    }
+                                };
+                            let mut poke = FriendPoke::default();
                             for templ in t.msg_templ_param {
-                                if templ.name == "uin_str1" {
-                                    sender = templ.value.parse::<i64>().unwrap_or_default()
-                                } else if templ.name == "uin_str2" {
-                                    receiver = templ.value.parse::<i64>().unwrap_or_default()
+                                match templ.name.as_str() {
+                                    "uin_str1" => {
+                                        poke.sender = templ.value.parse::<i64>().unwrap_or_default()
+                                    }
+                                    "uin_str2" => {
+                                        poke.receiver =
+                                            templ.value.parse::<i64>().unwrap_or_default()
+                                    }
+                                    "action_str" => poke.action_text = templ.value,
+                                    "suffix_str" => poke.suffix = templ.value,
+                                    _ => {}
                                 }
                             }
-                            if sender != 0 {
+                            if poke.sender != 0 {
                                 self.handler
                                     .handle(QEvent::FriendPoke(FriendPokeEvent {
                                         client: self.clone(),
-                                        poke: FriendPoke { sender, receiver },
+                                        poke,
                                     }))
                                     .await;
                             }
@@ -281,6 +502,12 @@ impl Client {
                                                     as i64,
                                                 group_name: new_group_name,
                                             };
+                                            let name = update.group_name.clone();
+                                            self.modify_cached_group_info(
+                                                update.group_code,
+                                                |info| info.name = name,
+                                            )
+                                            .await;
                                             self.handler
                                                 .handle(QEvent::GroupNameUpdate(
                                                     GroupNameUpdateEvent {
@@ -317,6 +544,10 @@ impl Client {
                                 if let Some(group) =
                                     self.find_group(group_sync_msg.grp_code, true).await
                                 {
+                                    let (group_code, owner_uin) = {
+                                        let info = group.info.read().await;
+                                        (info.code, info.owner_uin)
+                                    };
                                     let last_join_time = group
                                         .members
                                         .read()
@@ -325,12 +556,8 @@ impl Client {
                                         .map(|m| m.join_time)
                                         .max()
                                         .unwrap_or_default();
-                                    if let Ok(refreshed_members) = self
-                                        .get_group_member_list(
-                                            group.info.code,
-                                            group.info.owner_uin,
-                                        )
-                                        .await
+                                    if let Ok(refreshed_members) =
+                                        self.get_group_member_list(group_code, owner_uin).await
                                     {
                                         let mut members = group.members.write().await;
                                         members.clear();
@@ -343,8 +570,9 @@ impl Client {
                                         .iter()
                                         .filter(|m| m.join_time > last_join_time)
                                         .map(|m| NewMember {
-                                            group_code: group.info.code,
+                                            group_code,
                                             member_uin: m.uin,
+                                            join_method: JoinMethod::Unknown,
                                         })
                                         .collect();
                                     stream::iter(new_members)
@@ -409,6 +637,40 @@ impl Client {
                     ))
                     .await;
             }
+            PushTransInfo::OwnerChange(change) => {
+                self.modify_cached_group_info(change.group_code, |info| {
+                    info.owner_uin = change.new_owner_uin
+                })
+                .await;
+                // 转让后原群主变为普通成员
+                let changes = [
+                    (change.old_owner_uin, GroupMemberPermission::Member),
+                    (change.new_owner_uin, GroupMemberPermission::Owner),
+                ];
+                let group = self.groups.read().await.get(&change.group_code).cloned();
+                if let Some(group) = group {
+                    let mut members = group.members.write().await;
+                    for (uin, permission) in changes.iter() {
+                        if let Some(m) = members.iter_mut().find(|m| m.uin == *uin) {
+                            m.permission = permission.clone();
+                        }
+                    }
+                }
+                for (member_uin, new_permission) in changes {
+                    self.handler
+                        .handle(QEvent::MemberPermissionChange(
+                            MemberPermissionChangeEvent {
+                                client: self.clone(),
+                                change: MemberPermissionChange {
+                                    group_code: change.group_code,
+                                    member_uin,
+                                    new_permission,
+                                },
+                            },
+                        ))
+                        .await;
+                }
+            }
         }
     }
 
@@ -436,7 +698,7 @@ impl Client {
         push: pb::msg::PbPushMsg,
     ) -> RQResult<()> {
         let req = self.engine.read().await.build_delete_online_push_packet(
-            self.uin().await,
+            self.uin(),
             push.svrip(),
             Bytes::from(push.push_token.unwrap_or_default()),
             pkt_seq as u16,
@@ -444,7 +706,7 @@ impl Client {
         );
         let _ = self.send(req).await?;
         if let Some(msg) = push.msg {
-            self.process_message_sync(vec![msg]).await;
+            self.process_message_sync(stream::iter([Ok(msg)])).await;
         }
         Ok(())
     }
@@ -456,3 +718,166 @@ impl Client {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use jcers::JcePut;
+    use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+    use tokio::sync::RwLock;
+
+    use super::*;
+    use crate::client::Group;
+    use crate::engine::protocol::device::Device;
+    use crate::engine::protocol::version::ANDROID_WATCH;
+    use crate::engine::structs::{GroupInfo, GroupMemberInfo, GroupOwnerChange};
+
+    fn push_0x122(v_protobuf: Bytes) -> jce::PushMessageInfo {
+        let msg = jce::MsgType0x210 {
+            sub_msg_type: 0x122,
+            v_protobuf,
+        };
+        jce::PushMessageInfo {
+            msg_time: chrono::Utc::now().timestamp(),
+            msg_type: 528,
+            msg_seq: 1,
+            msg_uid: 1,
+            v_msg: msg.freeze(),
+            ..Default::default()
+        }
+    }
+
+    fn new_client() -> (Arc<Client>, UnboundedReceiver<QEvent>) {
+        let (tx, rx) = unbounded_channel();
+        (
+            Arc::new(Client::new(Device::random(), ANDROID_WATCH, tx)),
+            rx,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_friend_poke_gray_tip() {
+        let (client, mut rx) = new_client();
+        let param = |name: &str, value: &str| pb::notify::TemplParam {
+            name: name.into(),
+            value: value.into(),
+        };
+        let gray_tip = pb::notify::GeneralGrayTipInfo {
+            busi_type: 12,
+            busi_id: 1061,
+            msg_templ_param: vec![
+                param("uin_str1", "10001"),
+                param("uin_str2", "10002"),
+                param("action_str", "戳了戳"),
+                param("suffix_str", "的脸"),
+            ],
+            ..Default::default()
+        };
+        client
+            .process_push_req(vec![push_0x122(gray_tip.to_bytes())])
+            .await;
+        match rx.try_recv() {
+            Ok(QEvent::FriendPoke(e)) => {
+                assert_eq!(e.poke.sender, 10001);
+                assert_eq!(e.poke.receiver, 10002);
+                assert_eq!(e.poke.action_text, "戳了戳");
+                assert_eq!(e.poke.suffix, "的脸");
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    async fn cache_group(client: &Client, group_code: i64, owner_uin: i64, member_uin: i64) {
+        let member = |uin: i64, permission: GroupMemberPermission| GroupMemberInfo {
+            group_code,
+            uin,
+            permission,
+            ..Default::default()
+        };
+        client.groups.write().await.insert(
+            group_code,
+            Arc::new(Group {
+                info: RwLock::new(GroupInfo {
+                    code: group_code,
+                    owner_uin,
+                    ..Default::default()
+                }),
+                members: RwLock::new(vec![
+                    member(owner_uin, GroupMemberPermission::Owner),
+                    member(member_uin, GroupMemberPermission::Member),
+                ]),
+            }),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_owner_change_updates_cache() {
+        let (client, mut rx) = new_client();
+        cache_group(&client, 1000, 10001, 10002).await;
+        client
+            .process_push_trans(OnlinePushTrans {
+                msg_seq: 1,
+                msg_uid: 1,
+                msg_time: chrono::Utc::now().timestamp() as i32,
+                info: PushTransInfo::OwnerChange(GroupOwnerChange {
+                    group_code: 1000,
+                    old_owner_uin: 10001,
+                    new_owner_uin: 10002,
+                }),
+            })
+            .await;
+        assert_eq!(client.get_group_owner_uin(1000), Some(10002));
+        assert!(matches!(
+            client.get_member_permission(1000, 10001),
+            Some(GroupMemberPermission::Member)
+        ));
+        assert!(matches!(
+            client.get_member_permission(1000, 10002),
+            Some(GroupMemberPermission::Owner)
+        ));
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(QEvent::MemberPermissionChange(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_group_mute_all_updates_cache() {
+        let (client, _rx) = new_client();
+        cache_group(&client, 1000, 10001, 10002).await;
+        let mut v_msg = Vec::new();
+        v_msg.extend_from_slice(&1000u32.to_be_bytes());
+        v_msg.extend_from_slice(&[0x0c, 0]);
+        v_msg.extend_from_slice(&10001u32.to_be_bytes());
+        v_msg.extend_from_slice(&[0; 6]);
+        // target 为 0 表示全员禁言
+        v_msg.extend_from_slice(&0u32.to_be_bytes());
+        v_msg.extend_from_slice(&u32::MAX.to_be_bytes());
+        client
+            .process_push_req(vec![jce::PushMessageInfo {
+                msg_time: chrono::Utc::now().timestamp(),
+                msg_type: 732,
+                msg_seq: 2,
+                msg_uid: 2,
+                v_msg: Bytes::from(v_msg),
+                ..Default::default()
+            }])
+            .await;
+        let group = client.find_group(1000, false).await.unwrap();
+        assert_eq!(group.info.read().await.shut_up_timestamp, u32::MAX as i64);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_gray_tip_reports_decode_error() {
+        let (client, mut rx) = new_client();
+        client
+            .process_push_req(vec![push_0x122(Bytes::from_static(&[0xFF, 0xFF]))])
+            .await;
+        match rx.try_recv() {
+            Ok(QEvent::DecodeError(e)) => {
+                assert_eq!(e.command, commands::ONLINE_PUSH_REQ_PUSH);
+                assert_eq!(e.raw_body, Bytes::from_static(&[0xFF, 0xFF]));
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+}
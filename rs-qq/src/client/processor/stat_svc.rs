@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use crate::engine::jce;
 
-use crate::client::event::MSFOfflineEvent;
+use crate::client::event::{DisconnectReason, MSFOfflineEvent};
 use crate::client::Client;
 use crate::handler::QEvent;
 
@@ -15,7 +15,7 @@ impl Client {
         self.send_msg_offline_rsp(offline.uin, offline.seq_no)
             .await
             .ok();
-        self.stop();
+        self.stop_with_reason(DisconnectReason::Kicked);
         self.handler
             .handle(QEvent::MSFOffline(MSFOfflineEvent {
                 client: self.clone(),
@@ -1,9 +1,12 @@
+use std::sync::Arc;
+
+use crate::client::event::{LoginFailReason, LoginFailedEvent, LoginSucceededEvent};
 use crate::engine::command::wtlogin::*;
 use crate::handler::QEvent;
 use crate::Client;
 
 impl Client {
-    pub(crate) async fn process_login_response(&self, login_response: LoginResponse) {
+    pub(crate) async fn process_login_response(self: &Arc<Self>, login_response: LoginResponse) {
         if let LoginResponse::Success(ref success) = login_response {
             if let Some(info) = success.account_info.clone() {
                 let mut account_info = self.account_info.write().await;
@@ -12,11 +15,38 @@ impl Client {
                 account_info.gender = info.gender;
             }
         }
+        let fail_reason = match login_response {
+            LoginResponse::AccountFrozen => Some(LoginFailReason::BannedAccount),
+            LoginResponse::DeviceLocked(_) => Some(LoginFailReason::DeviceLock),
+            LoginResponse::TooManySMSRequest => {
+                Some(LoginFailReason::OtherError("too many sms request".into()))
+            }
+            LoginResponse::UnknownStatus(ref status) => {
+                Some(LoginFailReason::OtherError(status.message.clone()))
+            }
+            _ => None,
+        };
+        let success = matches!(login_response, LoginResponse::Success(_));
         self.engine
             .write()
             .await
             .process_login_response(login_response);
-        self.handler.handle(QEvent::Login(self.uin().await)).await;
+        if success {
+            self.handler
+                .handle(QEvent::LoginSucceeded(LoginSucceededEvent {
+                    client: self.clone(),
+                    uin: self.uin(),
+                    nickname: self.account_info.read().await.nickname.clone(),
+                }))
+                .await;
+        } else if let Some(reason) = fail_reason {
+            self.handler
+                .handle(QEvent::LoginFailed(LoginFailedEvent {
+                    client: self.clone(),
+                    reason,
+                }))
+                .await;
+        }
     }
 
     pub(crate) async fn process_trans_emp_response(&self, qrcode_state: QRCodeState) {
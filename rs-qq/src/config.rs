@@ -1,15 +1,42 @@
+use std::convert::TryFrom;
 use std::fmt::Debug;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
 
+use serde::Deserialize;
+
+use crate::client::ProxyConfig;
 use crate::engine::protocol::{
     device::Device,
     version::Version,
     version::{get_version, Protocol},
 };
+use crate::{RQError, RQResult};
+
+pub(crate) const DEFAULT_MESSAGE_DEDUP_CAPACITY: usize = 1000;
+pub(crate) const DEFAULT_TRANSLATE_MAX_RETRIES: u32 = 3;
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+const DEFAULT_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
 
 #[derive(Debug)]
 pub struct Config {
     pub device: Device,
     pub version: &'static Version,
+    /// TCP 接收缓冲区大小，None 使用系统默认值
+    pub tcp_recv_buffer: Option<usize>,
+    /// TCP 发送缓冲区大小，None 使用系统默认值
+    pub tcp_send_buffer: Option<usize>,
+    /// 用于群消息去重的 msg_uid 数量
+    pub message_dedup_capacity: usize,
+    /// send_and_wait 超时时间，默认 15 秒
+    pub request_timeout: Duration,
+    /// 心跳间隔，默认 30 秒
+    pub heartbeat_interval: Duration,
+    /// 翻译被限流时的最大重试次数，默认 3 次
+    pub translate_max_retries: u32,
+    /// SOCKS5 代理服务器，Client::connect 会通过代理连接
+    pub proxy: Option<ProxyConfig>,
 }
 
 impl Default for Config {
@@ -17,12 +44,286 @@ impl Default for Config {
         Self {
             device: Device::random(),
             version: get_version(Protocol::IPad),
+            tcp_recv_buffer: None,
+            tcp_send_buffer: None,
+            message_dedup_capacity: DEFAULT_MESSAGE_DEDUP_CAPACITY,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            heartbeat_interval: DEFAULT_HEARTBEAT_INTERVAL,
+            translate_max_retries: DEFAULT_TRANSLATE_MAX_RETRIES,
+            proxy: None,
         }
     }
 }
 
 impl Config {
     pub fn new(device: Device, version: &'static Version) -> Self {
-        Self { device, version }
+        Self {
+            device,
+            version,
+            tcp_recv_buffer: None,
+            tcp_send_buffer: None,
+            message_dedup_capacity: DEFAULT_MESSAGE_DEDUP_CAPACITY,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            heartbeat_interval: DEFAULT_HEARTBEAT_INTERVAL,
+            translate_max_retries: DEFAULT_TRANSLATE_MAX_RETRIES,
+            proxy: None,
+        }
+    }
+}
+
+// 配置文件内容，除 device 外都可以省略
+#[derive(Deserialize)]
+struct ConfigFile {
+    device: Option<Device>,
+    protocol: Option<String>,
+    tcp_recv_buffer: Option<usize>,
+    tcp_send_buffer: Option<usize>,
+    message_dedup_capacity: Option<usize>,
+    timeout_ms: Option<u64>,
+    heartbeat_ms: Option<u64>,
+    proxy_addr: Option<String>,
+}
+
+impl Config {
+    /// 从 TOML 配置文件读取，除 device 外的字段缺失时使用默认值
+    ///
+    /// ```toml
+    /// # IPad / AndroidPhone / AndroidWatch / MacOS / QiDian，默认 IPad
+    /// protocol = "IPad"
+    /// # TCP 缓冲区大小，不设置时使用系统默认值
+    /// # tcp_recv_buffer = 65536
+    /// # tcp_send_buffer = 65536
+    /// # 群消息去重记录的消息数量
+    /// message_dedup_capacity = 1000
+    /// # send_and_wait 超时时间（毫秒，大于 0），默认 15000
+    /// timeout_ms = 15000
+    /// # 心跳间隔（毫秒，大于 0），默认 30000
+    /// heartbeat_ms = 30000
+    /// # SOCKS5 代理服务器地址
+    /// # proxy_addr = "127.0.0.1:1080"
+    ///
+    /// # 必填，字段同 Device，可以由 Device::random() 生成后保存
+    /// [device]
+    /// display = "GMC.123456.001"
+    /// product = "iarim"
+    /// # ...
+    /// ```
+    pub fn from_toml_file(path: impl AsRef<Path>) -> RQResult<Config> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            RQError::IO(io::Error::new(
+                e.kind(),
+                format!("failed to read config file {}: {e}", path.display()),
+            ))
+        })?;
+        Self::from_toml_str(&content)
+    }
+
+    pub fn from_toml_str(content: &str) -> RQResult<Config> {
+        let file: ConfigFile = toml::from_str(content)
+            .map_err(|e| RQError::Decode(format!("invalid config file, {e}")))?;
+        let device = file.device.ok_or_else(|| {
+            RQError::InvalidArgument("config file is missing required field `device`".into())
+        })?;
+        let protocol = match file.protocol {
+            Some(p) => parse_protocol(&p)?,
+            None => Protocol::IPad,
+        };
+        let mut config = Config::new(device, get_version(protocol));
+        config.tcp_recv_buffer = file.tcp_recv_buffer;
+        config.tcp_send_buffer = file.tcp_send_buffer;
+        if let Some(capacity) = file.message_dedup_capacity {
+            config.message_dedup_capacity = capacity;
+        }
+        if let Some(ms) = file.timeout_ms {
+            config.request_timeout = non_zero_millis("timeout_ms", ms)?;
+        }
+        if let Some(ms) = file.heartbeat_ms {
+            config.heartbeat_interval = non_zero_millis("heartbeat_ms", ms)?;
+        }
+        if let Some(addr) = file.proxy_addr {
+            config.proxy = Some(parse_proxy_addr(&addr)?);
+        }
+        Ok(config)
+    }
+
+    /// 默认配置（随机设备）并应用环境变量，见 with_env_overrides
+    pub fn from_env() -> RQResult<Config> {
+        Config::default().with_env_overrides()
+    }
+
+    /// 使用环境变量覆盖配置，未设置的变量不会修改原有值
+    /// - RSQQ_PROTOCOL: IPad / AndroidPhone / AndroidWatch / MacOS / QiDian
+    /// - RSQQ_TIMEOUT_MS: send_and_wait 超时时间（毫秒，大于 0）
+    /// - RSQQ_HEARTBEAT_MS: 心跳间隔（毫秒，大于 0）
+    /// - RSQQ_PROXY_ADDR: SOCKS5 代理服务器地址，如 127.0.0.1:1080
+    pub fn with_env_overrides(mut self) -> RQResult<Config> {
+        if let Some(p) = env_var("RSQQ_PROTOCOL")? {
+            self.version = get_version(parse_protocol(&p)?);
+        }
+        if let Some(ms) = env_var("RSQQ_TIMEOUT_MS")? {
+            self.request_timeout = parse_millis("RSQQ_TIMEOUT_MS", &ms)?;
+        }
+        if let Some(ms) = env_var("RSQQ_HEARTBEAT_MS")? {
+            self.heartbeat_interval = parse_millis("RSQQ_HEARTBEAT_MS", &ms)?;
+        }
+        if let Some(addr) = env_var("RSQQ_PROXY_ADDR")? {
+            self.proxy = Some(parse_proxy_addr(&addr)?);
+        }
+        Ok(self)
+    }
+}
+
+// 未设置或为空时返回 None
+fn env_var(key: &str) -> RQResult<Option<String>> {
+    match std::env::var(key) {
+        Ok(v) if v.trim().is_empty() => Ok(None),
+        Ok(v) => Ok(Some(v.trim().to_string())),
+        Err(std::env::VarError::NotPresent) => Ok(None),
+        Err(e) => Err(RQError::InvalidArgument(format!("invalid {key}, {e}"))),
+    }
+}
+
+fn parse_protocol(p: &str) -> RQResult<Protocol> {
+    Protocol::try_from(p).map_err(|_| RQError::InvalidArgument(format!("unknown protocol `{p}`")))
+}
+
+fn parse_millis(key: &str, v: &str) -> RQResult<Duration> {
+    let ms = v
+        .parse()
+        .map_err(|e| RQError::InvalidArgument(format!("invalid {key} `{v}`, {e}")))?;
+    non_zero_millis(key, ms)
+}
+
+// 超时和心跳间隔为 0 没有意义，会导致请求立即超时或心跳空转
+fn non_zero_millis(key: &str, ms: u64) -> RQResult<Duration> {
+    if ms == 0 {
+        return Err(RQError::InvalidArgument(format!(
+            "{key} must be greater than 0"
+        )));
+    }
+    Ok(Duration::from_millis(ms))
+}
+
+fn parse_proxy_addr(addr: &str) -> RQResult<ProxyConfig> {
+    Ok(ProxyConfig {
+        addr: addr.parse().map_err(|e| {
+            RQError::InvalidArgument(format!("invalid proxy address `{addr}`, {e}"))
+        })?,
+        username: None,
+        password: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    // 只包含 device 的最小配置文件
+    fn minimal_toml() -> String {
+        toml::Value::try_from(HashMap::from([("device", Device::random())]))
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_from_toml_str_missing_device() {
+        assert!(matches!(
+            Config::from_toml_str("protocol = \"IPad\""),
+            Err(RQError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_toml_str_defaults() {
+        let config = Config::from_toml_str(&minimal_toml()).unwrap();
+        assert!(matches!(config.version.protocol, Protocol::IPad));
+        assert_eq!(config.tcp_recv_buffer, None);
+        assert_eq!(config.tcp_send_buffer, None);
+        assert_eq!(
+            config.message_dedup_capacity,
+            DEFAULT_MESSAGE_DEDUP_CAPACITY
+        );
+        assert_eq!(config.request_timeout, DEFAULT_REQUEST_TIMEOUT);
+        assert_eq!(config.heartbeat_interval, DEFAULT_HEARTBEAT_INTERVAL);
+        assert!(config.proxy.is_none());
+    }
+
+    #[test]
+    fn test_from_toml_str_zero_millis() {
+        for key in ["timeout_ms", "heartbeat_ms"] {
+            let content = format!("{key} = 0\n{}", minimal_toml());
+            assert!(matches!(
+                Config::from_toml_str(&content),
+                Err(RQError::InvalidArgument(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_millis() {
+        assert_eq!(
+            parse_millis("RSQQ_TIMEOUT_MS", "1500").unwrap(),
+            Duration::from_millis(1500)
+        );
+        assert!(parse_millis("RSQQ_TIMEOUT_MS", "0").is_err());
+        assert!(parse_millis("RSQQ_TIMEOUT_MS", "-1").is_err());
+        assert!(parse_millis("RSQQ_TIMEOUT_MS", "1s").is_err());
+    }
+
+    // 环境变量是进程级的，所有修改 RSQQ_* 的断言放在同一个测试中
+    #[test]
+    fn test_with_env_overrides() {
+        let keys = [
+            "RSQQ_PROTOCOL",
+            "RSQQ_TIMEOUT_MS",
+            "RSQQ_HEARTBEAT_MS",
+            "RSQQ_PROXY_ADDR",
+        ];
+        let clear = || keys.iter().for_each(|k| std::env::remove_var(k));
+
+        clear();
+        let config = Config::default().with_env_overrides().unwrap();
+        assert_eq!(config.request_timeout, DEFAULT_REQUEST_TIMEOUT);
+        assert!(config.proxy.is_none());
+
+        std::env::set_var("RSQQ_PROTOCOL", "AndroidWatch");
+        std::env::set_var("RSQQ_TIMEOUT_MS", " 5000 ");
+        std::env::set_var("RSQQ_HEARTBEAT_MS", "");
+        std::env::set_var("RSQQ_PROXY_ADDR", "127.0.0.1:1080");
+        let config = Config::default().with_env_overrides().unwrap();
+        assert!(matches!(config.version.protocol, Protocol::AndroidWatch));
+        assert_eq!(config.request_timeout, Duration::from_millis(5000));
+        // 空值视为未设置
+        assert_eq!(config.heartbeat_interval, DEFAULT_HEARTBEAT_INTERVAL);
+        assert_eq!(
+            config.proxy.map(|p| p.addr),
+            Some("127.0.0.1:1080".parse().unwrap())
+        );
+
+        std::env::set_var("RSQQ_HEARTBEAT_MS", "0");
+        assert!(matches!(
+            Config::default().with_env_overrides(),
+            Err(RQError::InvalidArgument(_))
+        ));
+
+        std::env::set_var("RSQQ_HEARTBEAT_MS", "1000");
+        std::env::set_var("RSQQ_PROTOCOL", "Symbian");
+        assert!(matches!(
+            Config::default().with_env_overrides(),
+            Err(RQError::InvalidArgument(_))
+        ));
+        clear();
+    }
+
+    #[test]
+    fn test_from_toml_str_unknown_protocol() {
+        let content = format!("protocol = \"Symbian\"\n{}", minimal_toml());
+        assert!(matches!(
+            Config::from_toml_str(&content),
+            Err(RQError::InvalidArgument(_))
+        ));
     }
 }
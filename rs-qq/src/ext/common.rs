@@ -1,16 +1,31 @@
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
+use crate::engine::command::common::PacketRequest;
 use crate::Client;
 
 /// 登录后必须执行的操作
 pub async fn after_login(client: &Arc<Client>) {
-    if let Err(err) = client.register_client().await {
-        tracing::error!("failed to register client: {}", err)
+    // 注册和拉取离线消息的包一起构建，按顺序发送
+    let mut packets = client
+        .build_packets(vec![
+            PacketRequest::ClientRegister,
+            PacketRequest::GetOfflineMsg {
+                last_message_time: client.last_message_time(),
+            },
+        ])
+        .await
+        .into_iter();
+    if let Some(register) = packets.next() {
+        if let Err(err) = client.send_register_packet(register).await {
+            tracing::error!("failed to register client: {}", err)
+        }
     }
     start_heartbeat(client.clone()).await;
-    if let Err(err) = client.refresh_status().await {
-        tracing::error!("failed to refresh status: {}", err)
+    if let Some(get_offline_msg) = packets.next() {
+        if let Err(err) = client.send_and_wait(get_offline_msg).await {
+            tracing::error!("failed to refresh status: {}", err)
+        }
     }
 }
 
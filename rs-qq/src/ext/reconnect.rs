@@ -12,7 +12,7 @@ use crate::{Client, RQError, RQResult};
 #[async_trait]
 pub trait Connector {
     async fn connect(&self, client: &Arc<Client>) -> std::io::Result<TcpStream> {
-        TcpStream::connect(client.get_address()).await
+        client.connect().await
     }
 }
 
@@ -35,6 +35,12 @@ pub async fn auto_reconnect<C: Connector + Sync>(
         tokio::time::sleep(interval).await;
         let stream = if let Ok(stream) = connector.connect(&client).await {
             count = 0;
+            // 自定义 Connector 没有通过 client.connect 连接时才使用 peer_addr
+            if client.server_address().await.is_none() {
+                if let Ok(addr) = stream.peer_addr() {
+                    client.set_server_address(addr).await;
+                }
+            }
             stream
         } else {
             count += 1;
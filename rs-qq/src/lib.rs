@@ -2,12 +2,13 @@
 #![feature(async_closure)]
 
 pub use client::handler;
-pub use client::Client;
+pub use client::{Client, ClientBuilder};
 pub use config::Config;
 pub use engine::command::wtlogin::{
     LoginDeviceLockLogin, LoginDeviceLocked, LoginNeedCaptcha, LoginResponse, LoginSuccess,
     LoginUnknownStatus, QRCodeConfirmed, QRCodeImageFetch, QRCodeState,
 };
+pub use engine::commands;
 pub use engine::error::{RQError, RQResult};
 use engine::jce;
 pub use engine::msg;
@@ -20,3 +21,22 @@ mod config;
 pub mod ext;
 pub(crate) mod internal;
 pub mod structs;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 公开类型需要能在多个线程间共享，新增字段导致丢失 Send / Sync 时编译失败
+    static_assertions::assert_impl_all!(Client: Send, Sync);
+    static_assertions::assert_impl_all!(handler::QEvent: Send, Sync);
+    static_assertions::assert_impl_all!(RQError: Send, Sync);
+    static_assertions::assert_impl_all!(msg::MessageChain: Send, Sync);
+    static_assertions::assert_impl_all!(structs::Group: Send, Sync);
+    static_assertions::assert_impl_all!(structs::GroupInfo: Send, Sync);
+    static_assertions::assert_impl_all!(structs::GroupMemberInfo: Send, Sync);
+    static_assertions::assert_impl_all!(structs::FriendInfo: Send, Sync);
+    static_assertions::assert_impl_all!(structs::GroupMessage: Send, Sync);
+    static_assertions::assert_impl_all!(structs::PrivateMessage: Send, Sync);
+    static_assertions::assert_impl_all!(Config: Send, Sync);
+    static_assertions::assert_impl_all!(ClientBuilder: Send, Sync);
+}
@@ -1,10 +1,65 @@
 use tokio::sync::RwLock;
 
+pub use crate::engine::command::oidb_svc::{
+    GroupFile, GroupFileCount, GroupFileList, GroupFolder, Language,
+};
 pub use crate::engine::structs::*;
+use crate::jce::SvcDevLoginInfo;
 
 // TODO 大群会占用大量内存，可以考虑提供 trait，用磁盘存储
 #[derive(Default, Debug)]
 pub struct Group {
-    pub info: GroupInfo,
+    // 收到群名、禁言、群主变更等推送时原地更新
+    pub info: RwLock<GroupInfo>,
     pub members: RwLock<Vec<GroupMemberInfo>>,
 }
+
+/// 通过安全验证的设备
+#[derive(Default, Debug, Clone)]
+pub struct DeviceListResponse {
+    pub devices: Vec<SvcDevLoginInfo>,
+    // 当前登录的设备，可以用来避免踢掉自己
+    pub current_session_info: Option<SvcDevLoginInfo>,
+}
+
+/// 群精华消息
+#[derive(Default, Debug, Clone)]
+pub struct EssenceMessage {
+    pub group_code: i64,
+    pub msg_seq: i32,
+    pub msg_rand: i32,
+    pub sender_uin: i64,
+    pub sender_nick: String,
+    pub sender_time: i64,
+    // 设精人
+    pub add_digest_uin: i64,
+    pub add_digest_nick: String,
+    pub add_digest_time: i64,
+}
+
+/// 空间说说
+#[derive(Default, Debug, Clone)]
+pub struct QzonePost {
+    pub tid: String,
+    pub owner_uin: i64,
+    pub owner_name: String,
+    pub content: String,
+    pub created_time: i64,
+    pub comment_count: u32,
+}
+
+/// 群投票结果
+#[derive(Default, Debug, Clone)]
+pub struct PollResult {
+    pub poll_id: String,
+    pub question: String,
+    pub options: Vec<PollOption>,
+    // 参与投票人数
+    pub voter_count: u32,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct PollOption {
+    pub text: String,
+    pub votes: u32,
+}